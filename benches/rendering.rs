@@ -1,15 +1,20 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
+use blocklet::font::{font_names, get_font};
+use blocklet::output::comment::{to_comment, CommentOptions};
+use blocklet::output::html::{to_html, HtmlOptions};
+use blocklet::output::literal::{to_literal, Language, LiteralOptions};
+use blocklet::output::png::write_png;
+use blocklet::output::svg::to_svg;
+use blocklet::output::ImageOptions;
+use blocklet::renderer::{render_text, RenderOptions, Renderer};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::io;
 
-// For benchmarks, we'll create our own simple render function
-fn render_text_simple(text: &str, font_name: &str, max_width: u32, height: u32) -> String {
-    // Mock implementation for benchmarking
-    // In a real scenario, this would be the actual render function
-    format!("Rendered: {} with {} ({}x{})", text, font_name, max_width, height)
-}
+// Every built-in font is 7 rows tall
+const HEIGHT: u32 = 7;
 
 fn benchmark_render_text(c: &mut Criterion) {
     let mut group = c.benchmark_group("render_text");
-    
+
     // Test different text lengths
     let texts = [
         ("SHORT", "HI"),
@@ -17,75 +22,181 @@ fn benchmark_render_text(c: &mut Criterion) {
         ("LONG", "THE QUICK BROWN FOX JUMPS OVER THE LAZY DOG"),
         ("VERY_LONG", "THIS IS A VERY LONG STRING THAT SHOULD TAKE MORE TIME TO RENDER AND ALLOWS US TO TEST THE PERFORMANCE OF OUR RENDERING ENGINE WITH EXTENDED TEXT"),
     ];
-    
-    let fonts = ["standard"];
-    
-    for font in fonts.iter() {
-        for (size_label, text) in texts.iter() {
-            group.bench_with_input(
-                BenchmarkId::new(format!("{}_{}", font, size_label), text.len()),
-                text,
-                |b, text| {
-                    b.iter(|| render_text_simple(black_box(text), black_box(font), 0, 5));
-                },
-            );
-        }
+
+    for (size_label, text) in texts.iter() {
+        group.bench_with_input(
+            BenchmarkId::new(*size_label, text.len()),
+            text,
+            |b, text| {
+                b.iter(|| render_text(black_box(text), black_box("standard"), 0, HEIGHT).unwrap());
+            },
+        );
     }
-    
+
     group.finish();
 }
 
 fn benchmark_word_wrapping(c: &mut Criterion) {
     let mut group = c.benchmark_group("word_wrapping");
-    
+
     let text = "HELLO WORLD TEST BENCHMARK PERFORMANCE UNICODE BLOCKS ASCII ART RENDERING";
-    let widths = [20, 40, 60, 80, 100];
-    
+    let widths = [0, 20, 40, 60, 80, 100];
+
     for width in widths.iter() {
-        group.bench_with_input(
-            BenchmarkId::new("wrapped", width),
-            width,
-            |b, &width| {
-                b.iter(|| render_text_simple(black_box(text), black_box("standard"), width, 5));
-            },
-        );
+        group.bench_with_input(BenchmarkId::new("wrapped", width), width, |b, &width| {
+            b.iter(|| render_text(black_box(text), black_box("standard"), width, HEIGHT).unwrap());
+        });
     }
-    
+
     group.finish();
 }
 
 fn benchmark_different_fonts(c: &mut Criterion) {
     let mut group = c.benchmark_group("font_comparison");
-    
+
     let text = "BENCHMARK TEST 123";
-    let fonts = ["standard"];
-    
-    for font in fonts.iter() {
-        group.bench_function(*font, |b| {
-            b.iter(|| render_text_simple(black_box(text), black_box(font), 0, 5));
+
+    for font in font_names() {
+        group.bench_function(font, |b| {
+            b.iter(|| render_text(black_box(text), black_box(font), 0, HEIGHT).unwrap());
         });
     }
-    
+
+    group.finish();
+}
+
+fn benchmark_font_loading(c: &mut Criterion) {
+    let mut group = c.benchmark_group("font_loading");
+
+    for font in font_names() {
+        group.bench_function(font, |b| {
+            b.iter(|| get_font(black_box(font)).unwrap());
+        });
+    }
+
+    group.bench_function("missing", |b| {
+        b.iter(|| get_font(black_box("nonexistent")).is_err());
+    });
+
     group.finish();
 }
 
 fn benchmark_character_types(c: &mut Criterion) {
     let mut group = c.benchmark_group("character_types");
-    
+
     let test_cases = [
         ("LETTERS_ONLY", "ABCDEFGHIJKLMNOPQRSTUVWXYZ"),
+        ("LOWERCASE", "abcdefghijklmnopqrstuvwxyz"),
         ("NUMBERS_ONLY", "0123456789"),
         ("MIXED", "ABC123XYZ789"),
         ("WITH_SPACES", "HELLO WORLD 123"),
         ("PUNCTUATION", "HELLO, WORLD! HOW ARE YOU?"),
+        ("UNSUPPORTED", "ÀÉÎÕÜ ¿¡ §¶"),
     ];
-    
+
     for (label, text) in test_cases.iter() {
         group.bench_function(*label, |b| {
-            b.iter(|| render_text_simple(black_box(text), black_box("standard"), 0, 5));
+            b.iter(|| render_text(black_box(text), black_box("standard"), 0, HEIGHT).unwrap());
         });
     }
-    
+
+    group.finish();
+}
+
+fn benchmark_streaming(c: &mut Criterion) {
+    let mut group = c.benchmark_group("streaming");
+
+    let text = "THE QUICK BROWN FOX JUMPS OVER THE LAZY DOG";
+    let options = RenderOptions {
+        font_name: "standard_shadow".to_string(),
+        max_width: 80,
+        height: HEIGHT,
+        spacing: 1,
+        ..RenderOptions::default()
+    };
+
+    // Baseline: build the whole banner as a String
+    group.bench_function("render_text_string", |b| {
+        b.iter(|| render_text(black_box(text), "standard_shadow", 80, HEIGHT).unwrap());
+    });
+
+    // Fresh renderer per call, streamed into a sink
+    group.bench_function("render_to_fresh", |b| {
+        b.iter(|| {
+            let mut renderer = Renderer::new(options.clone()).unwrap();
+            renderer
+                .render_to(&mut io::sink(), black_box(text))
+                .unwrap();
+        });
+    });
+
+    // One renderer reused across calls, as a per-request banner would be
+    let mut renderer = Renderer::new(options.clone()).unwrap();
+    group.bench_function("render_to_reused", |b| {
+        b.iter(|| {
+            renderer
+                .render_to(&mut io::sink(), black_box(text))
+                .unwrap()
+        });
+    });
+
+    group.finish();
+}
+
+fn benchmark_output_backends(c: &mut Criterion) {
+    let mut group = c.benchmark_group("output_backends");
+
+    // The art is rendered once so only the export itself is measured
+    let text = "HELLO WORLD";
+    let art = render_text(text, "standard_shadow", 0, HEIGHT).unwrap();
+    let image_options = ImageOptions::default();
+
+    group.bench_function("html", |b| {
+        let options = HtmlOptions {
+            full_page: true,
+            ..HtmlOptions::default()
+        };
+        b.iter(|| to_html(black_box(&art), &options));
+    });
+
+    group.bench_function("svg", |b| {
+        b.iter(|| to_svg(black_box(&art), &image_options));
+    });
+
+    group.bench_function("png", |b| {
+        b.iter(|| write_png(black_box(&art), &image_options, io::sink()).unwrap());
+    });
+
+    #[cfg(feature = "serde")]
+    group.bench_function("json", |b| {
+        let options = RenderOptions {
+            font_name: "standard_shadow".to_string(),
+            height: HEIGHT,
+            ..RenderOptions::default()
+        };
+        let mut renderer = Renderer::new(options).unwrap();
+        b.iter(|| {
+            let grid = renderer.render_grid(&[black_box(text)]).unwrap();
+            blocklet::output::json::to_json(&grid).unwrap()
+        });
+    });
+
+    group.bench_function("comment", |b| {
+        let options = CommentOptions::default();
+        b.iter(|| to_comment(black_box(&art), &options));
+    });
+
+    for language in [Language::Rust, Language::C, Language::Python] {
+        group.bench_with_input(
+            BenchmarkId::new("literal", format!("{:?}", language)),
+            &language,
+            |b, &language| {
+                let options = LiteralOptions::default();
+                b.iter(|| to_literal(black_box(&art), language, &options));
+            },
+        );
+    }
+
     group.finish();
 }
 
@@ -94,6 +205,9 @@ criterion_group!(
     benchmark_render_text,
     benchmark_word_wrapping,
     benchmark_different_fonts,
-    benchmark_character_types
+    benchmark_font_loading,
+    benchmark_character_types,
+    benchmark_streaming,
+    benchmark_output_backends
 );
 criterion_main!(benches);
@@ -0,0 +1,128 @@
+//! Compile-time blocklet banners.
+//!
+//! [`blocklet!`] renders text with the same fonts and renderer as the
+//! `blocklet` CLI while your crate compiles, and expands to a plain string
+//! literal. The banner costs nothing at runtime and your program does not
+//! depend on `blocklet` once it is built.
+//!
+//! ```
+//! use blocklet_macros::blocklet;
+//!
+//! const BANNER: &str = blocklet!("Hi", font = "standard_shadow");
+//! assert!(BANNER.starts_with("██╗   ██╗██╗"));
+//! assert_eq!(BANNER.lines().count(), 7);
+//! ```
+
+use blocklet::font::get_font;
+use blocklet::renderer::{RenderOptions, Renderer};
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_macro_input, Ident, Lit, LitBool, LitInt, LitStr, Token};
+
+/// Render text as block art at compile time, producing a `&'static str`.
+///
+/// The first argument is the text. Optional `key = value` arguments follow:
+///
+/// - `font = "standard_shadow"`: the built-in font to use (the default)
+/// - `width = 40`: wrap words to this many columns (`0`, the default, never wraps)
+/// - `ascii = true`: use plain ASCII instead of Unicode blocks
+///
+/// ```
+/// use blocklet_macros::blocklet;
+///
+/// let solid: &'static str = blocklet!("OK", font = "standard_solid", ascii = true);
+/// assert!(solid.is_ascii());
+/// assert!(!solid.contains('+'));
+/// ```
+///
+/// Unknown fonts and arguments are compile errors:
+///
+/// ```compile_fail
+/// use blocklet_macros::blocklet;
+///
+/// const BANNER: &str = blocklet!("Hi", font = "comic_sans");
+/// ```
+#[proc_macro]
+pub fn blocklet(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as BannerArgs);
+    match args.render() {
+        Ok(art) => quote!(#art).into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+struct BannerArgs {
+    text: LitStr,
+    font: Option<LitStr>,
+    width: u32,
+    ascii: bool,
+}
+
+impl Parse for BannerArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = BannerArgs {
+            text: input.parse()?,
+            font: None,
+            width: 0,
+            ascii: false,
+        };
+
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break; // Trailing comma
+            }
+            let key: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            match key.to_string().as_str() {
+                "font" => args.font = Some(input.parse()?),
+                "width" => args.width = input.parse::<LitInt>()?.base10_parse()?,
+                "ascii" => args.ascii = input.parse::<LitBool>()?.value,
+                _ => {
+                    // Consume the value so the error points at the key
+                    let _: Lit = input.parse()?;
+                    return Err(syn::Error::new(
+                        key.span(),
+                        "expected `font`, `width` or `ascii`",
+                    ));
+                }
+            }
+        }
+
+        Ok(args)
+    }
+}
+
+impl BannerArgs {
+    fn render(&self) -> syn::Result<String> {
+        let font_name = self
+            .font
+            .as_ref()
+            .map(LitStr::value)
+            .unwrap_or_else(|| "standard_shadow".to_string());
+        let font_span = self
+            .font
+            .as_ref()
+            .map(LitStr::span)
+            .unwrap_or_else(|| self.text.span());
+        let font = get_font(&font_name).map_err(|e| syn::Error::new(font_span, e))?;
+
+        let options = RenderOptions {
+            font_name,
+            max_width: self.width,
+            height: font.height,
+            spacing: 1,
+            ..RenderOptions::default()
+        };
+        let art = Renderer::with_font(font, options)
+            .render(&self.text.value())
+            .map_err(|e| syn::Error::new(self.text.span(), e))?;
+
+        Ok(if self.ascii {
+            blocklet::ascii::to_ascii(&art)
+        } else {
+            art
+        })
+    }
+}
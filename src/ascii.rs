@@ -0,0 +1,249 @@
+//! Plain ASCII fallback for rendered art, for files, serial consoles and log
+//! sinks that must not contain anything outside 7-bit ASCII.
+
+use crate::font::{Font, FontCharacter};
+use std::str::FromStr;
+
+/// What to do with the box-drawing strokes that make up a font's shadow
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AsciiShadow {
+    /// Draw them with `-`, `|` and `+`
+    Lines,
+    /// Leave them out, like the solid font
+    Drop,
+}
+
+impl FromStr for AsciiShadow {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lines" => Ok(AsciiShadow::Lines),
+            "none" => Ok(AsciiShadow::Drop),
+            _ => Err(format!(
+                "Unknown ASCII shadow style '{}' (expected lines or none)",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AsciiOptions {
+    /// Character drawn for filled blocks
+    pub fill: char,
+    pub shadow: AsciiShadow,
+}
+
+impl Default for AsciiOptions {
+    fn default() -> Self {
+        Self {
+            fill: '#',
+            shadow: AsciiShadow::Lines,
+        }
+    }
+}
+
+/// Replace the block and box-drawing characters in rendered art with ASCII:
+/// filled blocks become `#`, horizontal strokes `-`, vertical strokes `|` and
+/// corners or junctions `+`. Any other non-ASCII character becomes `?`.
+pub fn to_ascii(art: &str) -> String {
+    to_ascii_with(art, &AsciiOptions::default())
+}
+
+/// Like [`to_ascii`], with a choice of fill character and shadow handling
+pub fn to_ascii_with(art: &str, options: &AsciiOptions) -> String {
+    art.chars().map(|ch| ascii_char_with(ch, options)).collect()
+}
+
+/// The ASCII stand-in for a single character of rendered art
+pub fn ascii_char(ch: char) -> char {
+    ascii_char_with(ch, &AsciiOptions::default())
+}
+
+fn ascii_char_with(ch: char, options: &AsciiOptions) -> char {
+    let stroke = |c: char| match options.shadow {
+        AsciiShadow::Lines => c,
+        AsciiShadow::Drop => ' ',
+    };
+    match ch {
+        _ if ch.is_ascii() => ch,
+        '█' | '▓' | '▀' | '▄' | '▌' | '▐' | '▙' | '▛' | '▜' | '▟' => {
+            options.fill
+        }
+        '▒' | '░' | '▖' | '▗' | '▘' | '▝' | '▚' | '▞' => ':',
+        '─' | '━' | '═' | '╌' | '╍' | '┄' | '┅' | '┈' | '┉' | '╴' | '╶' | '╸' | '╺' => {
+            stroke('-')
+        }
+        '│' | '┃' | '║' | '╎' | '╏' | '┆' | '┇' | '┊' | '┋' | '╵' | '╷' | '╹' | '╻' => {
+            stroke('|')
+        }
+        '\u{2500}'..='\u{257F}' => stroke('+'),
+        _ => '?',
+    }
+}
+
+/// Spell plain text, such as a frame title, in ASCII: accented Latin letters
+/// lose their accents, typographic quotes and dashes become their ASCII
+/// forms, box drawing is converted like art and anything else becomes `?`
+pub fn transliterate(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        let spelled = match ch {
+            'À'..='Å' => "A",
+            'Æ' => "AE",
+            'Ç' => "C",
+            'È'..='Ë' => "E",
+            'Ì'..='Ï' => "I",
+            'Ð' => "D",
+            'Ñ' => "N",
+            'Ò'..='Ö' | 'Ø' => "O",
+            'Ù'..='Ü' => "U",
+            'Ý' => "Y",
+            'Þ' => "TH",
+            'ß' => "ss",
+            'à'..='å' => "a",
+            'æ' => "ae",
+            'ç' => "c",
+            'è'..='ë' => "e",
+            'ì'..='ï' => "i",
+            'ð' => "d",
+            'ñ' => "n",
+            'ò'..='ö' | 'ø' => "o",
+            'ù'..='ü' => "u",
+            'ý' | 'ÿ' => "y",
+            'þ' => "th",
+            '\u{a0}' => " ",
+            '‘' | '’' => "'",
+            '“' | '”' => "\"",
+            '‐' | '–' | '—' => "-",
+            '…' => "...",
+            '×' => "x",
+            _ => {
+                out.push(ascii_char(ch));
+                continue;
+            }
+        };
+        out.push_str(spelled);
+    }
+    out
+}
+
+/// Derive an ASCII-only font from any font by converting every glyph, so
+/// any font degrades the same way without hand-drawn ASCII versions.
+/// Glyph widths are unchanged because every character maps to exactly one.
+pub fn ascii_font(font: &Font, options: &AsciiOptions) -> Font {
+    let characters = font
+        .characters
+        .iter()
+        .map(|(ch, glyph)| {
+            let data = glyph
+                .data
+                .iter()
+                .map(|row| to_ascii_with(row, options))
+                .collect();
+            (
+                *ch,
+                FontCharacter {
+                    data,
+                    ..glyph.clone()
+                },
+            )
+        })
+        .collect();
+
+    Font {
+        name: format!("{}_ascii", font.name),
+        description: format!("{} (ASCII)", font.description),
+        height: font.height,
+        characters,
+        source: font.source.clone(),
+    }
+}
+
+/// Whether the locale from the environment can display UTF-8.
+///
+/// Follows the usual precedence of `LC_ALL`, `LC_CTYPE` and `LANG`. When none
+/// is set the locale is unknown and Unicode output is assumed, since most
+/// modern terminals handle it regardless.
+pub fn locale_supports_unicode() -> bool {
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .map(|value| is_utf8_locale(&value))
+        .unwrap_or(true)
+}
+
+/// Whether a locale name such as `en_US.UTF-8` uses the UTF-8 codeset
+pub fn is_utf8_locale(locale: &str) -> bool {
+    let codeset = locale.split_once('.').map(|(_, rest)| rest).unwrap_or("");
+    let codeset = codeset.split('@').next().unwrap_or("").to_ascii_lowercase();
+    codeset == "utf-8" || codeset == "utf8"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font::get_font;
+    use crate::renderer::{render_text, RenderOptions, Renderer};
+
+    #[test]
+    fn test_shadow_font_to_ascii() {
+        let art = render_text("HI", "standard_shadow", 0, 7).unwrap();
+        let ascii = to_ascii(&art);
+        assert!(ascii.is_ascii());
+        assert_eq!(ascii.lines().next().unwrap(), "##+   ##+##+");
+        assert_eq!(ascii.lines().nth(5).unwrap(), "+-+   +-++-+");
+    }
+
+    #[test]
+    fn test_transliterate() {
+        assert_eq!(transliterate("Café ✓"), "Cafe ?");
+        assert_eq!(transliterate("Øre – “Straße”…"), "Ore - \"Strasse\"...");
+        assert_eq!(transliterate("╔═╗ ok"), "+-+ ok");
+    }
+
+    #[test]
+    fn test_fill_and_dropped_shadow() {
+        let options = AsciiOptions {
+            fill: '@',
+            shadow: AsciiShadow::Drop,
+        };
+        assert_eq!(to_ascii_with("██╗\n╚═╝", &options), "@@ \n   ");
+    }
+
+    #[test]
+    fn test_ascii_font_matches_converted_art() {
+        let options = AsciiOptions::default();
+        let font = ascii_font(get_font("standard_shadow").unwrap(), &options);
+        assert_eq!(font.name, "standard_shadow_ascii");
+        assert!(font
+            .characters
+            .values()
+            .all(|glyph| glyph.data.iter().all(|row| row.is_ascii())));
+
+        let render_options = RenderOptions {
+            height: 7,
+            max_width: 30,
+            ..RenderOptions::default()
+        };
+        let ascii = Renderer::with_font(&font, render_options)
+            .render("HELLO WORLD")
+            .unwrap();
+        assert_eq!(
+            ascii,
+            to_ascii(&render_text("HELLO WORLD", "standard_shadow", 30, 7).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_utf8_locale_names() {
+        assert!(is_utf8_locale("en_US.UTF-8"));
+        assert!(is_utf8_locale("C.utf8"));
+        assert!(is_utf8_locale("de_DE.UTF-8@euro"));
+        assert!(!is_utf8_locale("C"));
+        assert!(!is_utf8_locale("POSIX"));
+        assert!(!is_utf8_locale("en_US.ISO-8859-1"));
+    }
+}
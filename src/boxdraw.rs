@@ -0,0 +1,110 @@
+//! The arms of box-drawing characters, shared by everything that draws or
+//! transforms them: the graphical backends, scaling, rotation and mirroring.
+
+/// Line weight of one arm of a box-drawing character
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Weight {
+    None,
+    Light,
+    Heavy,
+    Double,
+}
+
+/// Arms of a box-drawing character: left, right, up, down
+pub(crate) type Arms = [Weight; 4];
+
+pub(crate) const LEFT: usize = 0;
+pub(crate) const RIGHT: usize = 1;
+pub(crate) const UP: usize = 2;
+pub(crate) const DOWN: usize = 3;
+
+/// The arms of `ch`, or `None` when it is not a box-drawing character
+pub(crate) fn box_arms(ch: char) -> Option<Arms> {
+    use Weight::{Double as D, Heavy as H, Light as L, None as N};
+    let arms = match ch {
+        '─' => [L, L, N, N],
+        '━' => [H, H, N, N],
+        '│' => [N, N, L, L],
+        '┃' => [N, N, H, H],
+        '┌' => [N, L, N, L],
+        '┐' => [L, N, N, L],
+        '└' => [N, L, L, N],
+        '┘' => [L, N, L, N],
+        '┏' => [N, H, N, H],
+        '┓' => [H, N, N, H],
+        '┗' => [N, H, H, N],
+        '┛' => [H, N, H, N],
+        '├' => [N, L, L, L],
+        '┤' => [L, N, L, L],
+        '┬' => [L, L, N, L],
+        '┴' => [L, L, L, N],
+        '┼' => [L, L, L, L],
+        '┣' => [N, H, H, H],
+        '┫' => [H, N, H, H],
+        '┳' => [H, H, N, H],
+        '┻' => [H, H, H, N],
+        '╋' => [H, H, H, H],
+        '╴' => [L, N, N, N],
+        '╶' => [N, L, N, N],
+        '╵' => [N, N, L, N],
+        '╷' => [N, N, N, L],
+        '═' => [D, D, N, N],
+        '║' => [N, N, D, D],
+        '╔' => [N, D, N, D],
+        '╗' => [D, N, N, D],
+        '╚' => [N, D, D, N],
+        '╝' => [D, N, D, N],
+        '╠' => [N, D, D, D],
+        '╣' => [D, N, D, D],
+        '╦' => [D, D, N, D],
+        '╩' => [D, D, D, N],
+        '╬' => [D, D, D, D],
+        // Rounded corners have the arms of the square ones
+        '╭' => [N, L, N, L],
+        '╮' => [L, N, N, L],
+        '╰' => [N, L, L, N],
+        '╯' => [L, N, L, N],
+        _ => return None,
+    };
+    Some(arms)
+}
+
+/// The straight stroke of `weight` running horizontally or vertically
+pub(crate) fn stroke(weight: Weight, horizontal: bool) -> char {
+    match (weight, horizontal) {
+        (Weight::None, _) => ' ',
+        (Weight::Light, true) => '─',
+        (Weight::Light, false) => '│',
+        (Weight::Heavy, true) => '━',
+        (Weight::Heavy, false) => '┃',
+        (Weight::Double, true) => '═',
+        (Weight::Double, false) => '║',
+    }
+}
+
+/// The box-drawing character with exactly these arms, if there is one.
+/// Corners are square.
+pub(crate) fn from_arms(arms: Arms) -> Option<char> {
+    ('\u{2500}'..='\u{257F}').find(|&ch| box_arms(ch) == Some(arms))
+}
+
+/// Whether `ch` is a rounded corner
+pub(crate) fn is_arc(ch: char) -> bool {
+    matches!(ch, '╭' | '╮' | '╰' | '╯')
+}
+
+/// The character with these arms drawn like `ch`, so turning or mirroring
+/// a rounded corner gives another rounded corner
+pub(crate) fn restyle(ch: char, arms: Arms) -> Option<char> {
+    let square = from_arms(arms)?;
+    if !is_arc(ch) {
+        return Some(square);
+    }
+    Some(match square {
+        '┌' => '╭',
+        '┐' => '╮',
+        '└' => '╰',
+        '┘' => '╯',
+        other => other,
+    })
+}
@@ -0,0 +1,97 @@
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ColorError {
+    #[error("Invalid color '{0}' (expected #rgb, #rrggbb or a color name)")]
+    Invalid(String),
+}
+
+/// An sRGB color used by the graphical output backends
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Format as a `#rrggbb` hex string, as used by CSS and SVG
+    pub fn to_hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_hex())
+    }
+}
+
+impl FromStr for Color {
+    type Err = ColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ColorError::Invalid(s.to_string());
+        let value = s.trim();
+
+        if let Some(hex) = value.strip_prefix('#') {
+            let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).map_err(|_| invalid());
+            let byte = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| invalid());
+            // from_str_radix would also accept a sign, as in "#+f+f+f"
+            if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(invalid());
+            }
+            return match hex.len() {
+                3 => Ok(Color::rgb(digit(0)? * 17, digit(1)? * 17, digit(2)? * 17)),
+                6 => Ok(Color::rgb(byte(0)?, byte(2)?, byte(4)?)),
+                _ => Err(invalid()),
+            };
+        }
+
+        let color = match value.to_ascii_lowercase().as_str() {
+            "black" => Color::BLACK,
+            "white" => Color::WHITE,
+            "red" => Color::rgb(205, 49, 49),
+            "green" => Color::rgb(13, 188, 121),
+            "yellow" => Color::rgb(229, 229, 16),
+            "blue" => Color::rgb(36, 114, 200),
+            "magenta" => Color::rgb(188, 63, 188),
+            "cyan" => Color::rgb(17, 168, 205),
+            "orange" => Color::rgb(255, 140, 0),
+            "gray" | "grey" => Color::rgb(128, 128, 128),
+            _ => return Err(invalid()),
+        };
+        Ok(color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hex() {
+        assert_eq!("#ff8000".parse::<Color>().unwrap(), Color::rgb(255, 128, 0));
+        assert_eq!("#fff".parse::<Color>().unwrap(), Color::WHITE);
+        assert!("#ff80".parse::<Color>().is_err());
+        assert!("#gggggg".parse::<Color>().is_err());
+        assert!("#+f+f+f".parse::<Color>().is_err());
+        assert!("#+ff".parse::<Color>().is_err());
+    }
+
+    #[test]
+    fn test_parse_name() {
+        assert_eq!("Black".parse::<Color>().unwrap(), Color::BLACK);
+        assert_eq!("grey".parse::<Color>().unwrap().to_hex(), "#808080");
+        assert!("chartreuse-ish".parse::<Color>().is_err());
+    }
+}
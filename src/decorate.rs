@@ -0,0 +1,344 @@
+//! Decorations drawn around rendered art, such as frames for MOTD panels.
+
+use crate::ascii::transliterate;
+use std::str::FromStr;
+use unicode_width::UnicodeWidthStr;
+
+/// Frame styles for `--border`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BorderStyle {
+    Single,
+    Double,
+    Rounded,
+    Heavy,
+    Block,
+    /// A single frame with a solid drop shadow below and to the right
+    Shadow,
+}
+
+impl BorderStyle {
+    /// Names accepted by `--border`
+    pub const NAMES: &'static [&'static str] =
+        &["single", "double", "rounded", "heavy", "block", "shadow"];
+
+    /// Characters for the top-left, top-right, bottom-left and bottom-right
+    /// corners, then the horizontal and vertical edges
+    fn chars(self, ascii: bool) -> [char; 6] {
+        if ascii {
+            return match self {
+                BorderStyle::Block => ['#'; 6],
+                BorderStyle::Double => ['+', '+', '+', '+', '=', '|'],
+                _ => ['+', '+', '+', '+', '-', '|'],
+            };
+        }
+        match self {
+            BorderStyle::Single | BorderStyle::Shadow => ['┌', '┐', '└', '┘', '─', '│'],
+            BorderStyle::Double => ['╔', '╗', '╚', '╝', '═', '║'],
+            BorderStyle::Rounded => ['╭', '╮', '╰', '╯', '─', '│'],
+            BorderStyle::Heavy => ['┏', '┓', '┗', '┛', '━', '┃'],
+            BorderStyle::Block => ['█'; 6],
+        }
+    }
+}
+
+impl FromStr for BorderStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "single" => Ok(BorderStyle::Single),
+            "double" => Ok(BorderStyle::Double),
+            "rounded" => Ok(BorderStyle::Rounded),
+            "heavy" => Ok(BorderStyle::Heavy),
+            "block" => Ok(BorderStyle::Block),
+            "shadow" => Ok(BorderStyle::Shadow),
+            _ => Err(format!("Unknown border style '{}'", s)),
+        }
+    }
+}
+
+/// Space around each side of the art, in rows (top, bottom) and columns
+/// (left, right)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Spacing {
+    pub top: usize,
+    pub right: usize,
+    pub bottom: usize,
+    pub left: usize,
+}
+
+impl Spacing {
+    pub const fn new(top: usize, right: usize, bottom: usize, left: usize) -> Self {
+        Self {
+            top,
+            right,
+            bottom,
+            left,
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl FromStr for Spacing {
+    type Err = String;
+
+    /// Parse CSS-style shorthand: `all`, `vertical horizontal`,
+    /// `top horizontal bottom` or `top right bottom left`, separated by
+    /// spaces or commas
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values = s
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|v| !v.is_empty())
+            .map(|v| v.parse::<usize>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| {
+                format!(
+                    "Invalid spacing '{}' (expected 1 to 4 numbers, e.g. \"1 2\")",
+                    s
+                )
+            })?;
+        match values[..] {
+            [all] => Ok(Spacing::new(all, all, all, all)),
+            [vertical, horizontal] => Ok(Spacing::new(vertical, horizontal, vertical, horizontal)),
+            [top, horizontal, bottom] => Ok(Spacing::new(top, horizontal, bottom, horizontal)),
+            [top, right, bottom, left] => Ok(Spacing::new(top, right, bottom, left)),
+            _ => Err(format!(
+                "Invalid spacing '{}' (expected 1 to 4 numbers, e.g. \"1 2\")",
+                s
+            )),
+        }
+    }
+}
+
+/// Surround art with `spacing`, filling the new area with `fill`.
+///
+/// Rows are first padded to the width of the widest row so the art forms a
+/// rectangle, and that padding uses `fill` too. Used with a space for margins
+/// and with any character for a filled padding area.
+pub fn pad(art: &str, spacing: Spacing, fill: char) -> String {
+    let width = art
+        .lines()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0);
+    let total = spacing.left + width + spacing.right;
+    let blank: String = std::iter::repeat_n(fill, total).collect();
+
+    let mut rows = vec![blank.clone(); spacing.top];
+    for line in art.lines() {
+        let mut row = String::with_capacity(total);
+        row.extend(std::iter::repeat_n(fill, spacing.left));
+        row.push_str(line);
+        row.extend(std::iter::repeat_n(
+            fill,
+            total - spacing.left - line.chars().count(),
+        ));
+        rows.push(row);
+    }
+    rows.extend(std::iter::repeat_n(blank, spacing.bottom));
+    rows.join("\n")
+}
+
+/// Check that a frame title or footer fits on one line of the frame
+pub fn parse_label(label: &str) -> Result<String, String> {
+    if label.chars().any(|ch| ch.is_control()) {
+        Err("a title or footer must be a single line of text".to_string())
+    } else {
+        Ok(label.to_string())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FrameOptions {
+    pub style: BorderStyle,
+    /// Plain text set into the top edge
+    pub title: Option<String>,
+    /// Plain text set into the bottom edge
+    pub footer: Option<String>,
+    /// Draw the frame with ASCII characters
+    pub ascii: bool,
+}
+
+impl Default for FrameOptions {
+    fn default() -> Self {
+        Self {
+            style: BorderStyle::Single,
+            title: None,
+            footer: None,
+            ascii: false,
+        }
+    }
+}
+
+/// Draw a frame around rendered art.
+///
+/// The frame hugs the widest row; pad the art first for space inside it. The
+/// frame grows to fit a title or footer wider than the art.
+pub fn frame(art: &str, options: &FrameOptions) -> String {
+    let [top_left, top_right, bottom_left, bottom_right, horizontal, vertical] =
+        options.style.chars(options.ascii);
+    // ASCII frames keep their titles ASCII too, and line breaks would tear
+    // the edge apart
+    let label = |label: &Option<String>| {
+        label.as_ref().map(|text| {
+            let text: String = text
+                .chars()
+                .map(|ch| if ch.is_control() { ' ' } else { ch })
+                .collect();
+            if options.ascii {
+                transliterate(&text)
+            } else {
+                text
+            }
+        })
+    };
+    let (title, footer) = (label(&options.title), label(&options.footer));
+    let lines: Vec<&str> = art.lines().collect();
+    let art_width = lines
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    // Labels sit one edge character in from each corner, surrounded by
+    // spaces, and are measured in terminal columns
+    let label_width = |label: &Option<String>| label.as_ref().map_or(0, |l| l.width() + 4);
+    let inner = art_width.max(label_width(&title)).max(label_width(&footer));
+
+    let edge = |left: char, right: char, label: &Option<String>| {
+        let mut row = String::new();
+        row.push(left);
+        let mut used = 0;
+        if let Some(label) = label {
+            row.push(horizontal);
+            row.push(' ');
+            row.push_str(label);
+            row.push(' ');
+            used = label.width() + 3;
+        }
+        row.extend(std::iter::repeat_n(horizontal, inner - used));
+        row.push(right);
+        row
+    };
+
+    let mut rows = vec![edge(top_left, top_right, &title)];
+    for line in &lines {
+        let fill = inner - line.chars().count();
+        let mut row = String::new();
+        row.push(vertical);
+        row.push_str(line);
+        row.extend(std::iter::repeat_n(' ', fill));
+        row.push(vertical);
+        rows.push(row);
+    }
+    rows.push(edge(bottom_left, bottom_right, &footer));
+
+    if options.style == BorderStyle::Shadow {
+        let shade = if options.ascii { '#' } else { '█' };
+        let width = inner + 2;
+        for row in rows.iter_mut().skip(1) {
+            row.push(shade);
+        }
+        rows[0].push(' ');
+        let mut bottom = String::from(" ");
+        bottom.extend(std::iter::repeat_n(shade, width));
+        rows.push(bottom);
+    }
+
+    rows.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_spacing() {
+        assert_eq!("2".parse::<Spacing>().unwrap(), Spacing::new(2, 2, 2, 2));
+        assert_eq!("1 3".parse::<Spacing>().unwrap(), Spacing::new(1, 3, 1, 3));
+        assert_eq!(
+            "1,2,3".parse::<Spacing>().unwrap(),
+            Spacing::new(1, 2, 3, 2)
+        );
+        assert_eq!(
+            "1 2 3 4".parse::<Spacing>().unwrap(),
+            Spacing::new(1, 2, 3, 4)
+        );
+        assert!("".parse::<Spacing>().is_err());
+        assert!("1 2 3 4 5".parse::<Spacing>().is_err());
+        assert!("-1".parse::<Spacing>().is_err());
+    }
+
+    #[test]
+    fn test_pad() {
+        assert_eq!(
+            pad("AB\nC", Spacing::new(1, 1, 0, 2), '.'),
+            ".....\n..AB.\n..C.."
+        );
+        assert_eq!(pad("AB\nC", Spacing::default(), ' '), "AB\nC ");
+    }
+
+    #[test]
+    fn test_single_frame() {
+        let framed = frame(
+            &pad("AB\nC", Spacing::new(0, 1, 0, 1), ' '),
+            &FrameOptions::default(),
+        );
+        assert_eq!(framed, "┌────┐\n│ AB │\n│ C  │\n└────┘");
+    }
+
+    #[test]
+    fn test_title_and_footer() {
+        let options = FrameOptions {
+            style: BorderStyle::Double,
+            title: Some("Status".to_string()),
+            footer: Some("ok".to_string()),
+            ascii: false,
+        };
+        let framed = frame("██", &options);
+        let rows: Vec<&str> = framed.lines().collect();
+        assert_eq!(rows[0], "╔═ Status ═╗");
+        assert_eq!(rows[1], "║██        ║");
+        assert_eq!(rows[2], "╚═ ok ═════╝");
+    }
+
+    #[test]
+    fn test_label_width_and_line_breaks() {
+        // Wide characters take two columns each
+        let options = FrameOptions {
+            title: Some("日本".to_string()),
+            footer: Some("a\nb".to_string()),
+            ..FrameOptions::default()
+        };
+        let framed = frame("", &options);
+        let rows: Vec<&str> = framed.lines().collect();
+        assert_eq!(rows[0], "┌─ 日本 ─┐");
+        assert_eq!(rows[1], "└─ a b ──┘");
+
+        assert_eq!(parse_label("Status").unwrap(), "Status");
+        assert!(parse_label("two\nlines").is_err());
+    }
+
+    #[test]
+    fn test_shadow_and_ascii() {
+        let options = FrameOptions {
+            style: BorderStyle::Shadow,
+            ascii: true,
+            ..FrameOptions::default()
+        };
+        assert_eq!(frame(" A ", &options), "+---+ \n| A |#\n+---+#\n #####");
+
+        let options = FrameOptions {
+            style: BorderStyle::Single,
+            title: Some("Café ✓".to_string()),
+            ascii: true,
+            ..FrameOptions::default()
+        };
+        let framed = frame("A", &options);
+        assert!(framed.is_ascii());
+        assert!(framed.starts_with("+- Cafe ? -+"));
+    }
+}
@@ -0,0 +1,792 @@
+//! `blocklet font edit`: a terminal editor for font files.
+//!
+//! The editor shows one glyph at a time as a grid of cells. Cells are painted
+//! with a brush: full and half blocks, or double and light lines that join up
+//! with the box-drawing characters around them. A glyph's drop shadow can be
+//! regenerated from its body, and a line of preview text is rendered with the
+//! font as it is edited. Saving writes the font back in the font file format.
+
+use crate::boxdraw::{box_arms, from_arms, stroke, Arms, Weight, DOWN, LEFT, RIGHT, UP};
+use crate::font::{Font, FontCharacter, FontSource, FALLBACK_CHARACTER};
+use crate::fontfile;
+use crate::renderer::{RenderOptions, Renderer};
+use crate::transform;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::{cursor, queue, terminal};
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+/// What painting a cell draws
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Brush {
+    Block,
+    UpperHalf,
+    LowerHalf,
+    /// Double lines, the weight of the built-in shadows
+    Double,
+    Light,
+}
+
+impl Brush {
+    pub const ALL: [Brush; 5] = [
+        Brush::Block,
+        Brush::UpperHalf,
+        Brush::LowerHalf,
+        Brush::Double,
+        Brush::Light,
+    ];
+
+    fn symbol(self) -> char {
+        match self {
+            Brush::Block => '█',
+            Brush::UpperHalf => '▀',
+            Brush::LowerHalf => '▄',
+            Brush::Double => '═',
+            Brush::Light => '─',
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Brush::Block => "block",
+            Brush::UpperHalf => "upper half",
+            Brush::LowerHalf => "lower half",
+            Brush::Double => "double line",
+            Brush::Light => "light line",
+        }
+    }
+
+    /// The line weight of a line brush
+    fn weight(self) -> Option<Weight> {
+        match self {
+            Brush::Double => Some(Weight::Double),
+            Brush::Light => Some(Weight::Light),
+            _ => None,
+        }
+    }
+}
+
+/// A line of text being typed at the bottom of the screen
+enum Prompt {
+    /// The character of a glyph to go to, or to add
+    Glyph(String),
+    Preview(String),
+}
+
+const HELP: &str = "arrows move  shift+arrows draw  space paint  x erase  1-5/b brush  [ ] width  s shadow  g glyph  p preview  w save  q quit";
+
+pub struct Editor {
+    font: Font,
+    path: PathBuf,
+    /// The font's characters in code point order
+    chars: Vec<char>,
+    index: usize,
+    /// Column and row of the selected cell
+    cursor: (usize, usize),
+    brush: Brush,
+    preview: String,
+    prompt: Option<Prompt>,
+    modified: bool,
+    /// Set by a first quit with unsaved changes, so a second one quits
+    confirm_quit: bool,
+    message: String,
+}
+
+/// An empty font to start editing from, with a blank space and `?` glyph
+pub fn blank_font(name: &str, height: u32, path: PathBuf) -> Font {
+    let glyph = |width: u32| FontCharacter {
+        width,
+        height,
+        data: vec![" ".repeat(width as usize); height as usize],
+    };
+    let characters = [
+        (' ', glyph((height / 2).max(1))),
+        (FALLBACK_CHARACTER, glyph(height)),
+    ]
+    .into_iter()
+    .collect();
+    Font {
+        name: name.to_string(),
+        description: String::new(),
+        height,
+        characters,
+        source: FontSource::File(path),
+    }
+}
+
+impl Editor {
+    /// Edit `font`, saving it to `path`. Every glyph is made as wide as its
+    /// widest row and as tall as the font, so the saved file always loads.
+    pub fn new(mut font: Font, path: PathBuf) -> Self {
+        let height = font.height;
+        for glyph in font.characters.values_mut() {
+            let width = glyph
+                .data
+                .iter()
+                .map(|row| row.chars().count())
+                .max()
+                .unwrap_or(glyph.width as usize);
+            glyph.data.resize(height as usize, String::new());
+            for row in &mut glyph.data {
+                let padding = width - row.chars().count().min(width);
+                row.extend(std::iter::repeat_n(' ', padding));
+            }
+            glyph.width = width as u32;
+            glyph.height = height;
+        }
+
+        let mut chars: Vec<char> = font.characters.keys().copied().collect();
+        chars.sort_unstable();
+        // Start on the first letter rather than punctuation when there is one
+        let index = chars.iter().position(|ch| ch.is_alphabetic()).unwrap_or(0);
+        Editor {
+            font,
+            path,
+            chars,
+            index,
+            cursor: (0, 0),
+            brush: Brush::Block,
+            preview: "Blocklet".to_string(),
+            prompt: None,
+            modified: false,
+            confirm_quit: false,
+            message: String::new(),
+        }
+    }
+
+    pub fn font(&self) -> &Font {
+        &self.font
+    }
+
+    pub fn is_modified(&self) -> bool {
+        self.modified
+    }
+
+    /// The character of the glyph being edited
+    pub fn current(&self) -> char {
+        self.chars[self.index]
+    }
+
+    fn glyph(&self) -> &FontCharacter {
+        &self.font.characters[&self.current()]
+    }
+
+    fn size(&self) -> (usize, usize) {
+        (self.glyph().width as usize, self.font.height as usize)
+    }
+
+    fn cell(&self, x: usize, y: usize) -> char {
+        self.glyph().data[y].chars().nth(x).unwrap_or(' ')
+    }
+
+    fn set(&mut self, x: usize, y: usize, ch: char) {
+        let ch_key = self.current();
+        let row = &mut self
+            .font
+            .characters
+            .get_mut(&ch_key)
+            .expect("current glyph exists")
+            .data[y];
+        *row = row
+            .chars()
+            .enumerate()
+            .map(|(i, c)| if i == x { ch } else { c })
+            .collect();
+        self.modified = true;
+    }
+
+    /// The cell one step from (`x`, `y`) towards `direction`, if it is in the glyph
+    fn neighbour(&self, x: usize, y: usize, direction: usize) -> Option<(usize, usize)> {
+        let (width, height) = self.size();
+        match direction {
+            LEFT if x > 0 => Some((x - 1, y)),
+            RIGHT if x + 1 < width => Some((x + 1, y)),
+            UP if y > 0 => Some((x, y - 1)),
+            DOWN if y + 1 < height => Some((x, y + 1)),
+            _ => None,
+        }
+    }
+
+    /// Paint the selected cell with the brush. A line joins the lines
+    /// around it that reach towards the cell, and also reaches `towards`
+    /// a neighbour when it is drawn by dragging.
+    pub fn paint(&mut self, towards: Option<usize>) {
+        let (x, y) = self.cursor;
+        let Some(weight) = self.brush.weight() else {
+            self.set(x, y, self.brush.symbol());
+            return;
+        };
+
+        let mut arms: Arms = [Weight::None; 4];
+        for direction in [LEFT, RIGHT, UP, DOWN] {
+            if self.reaches_back(x, y, direction) {
+                arms[direction] = weight;
+            }
+        }
+        if let Some(direction) =
+            towards.filter(|&direction| self.neighbour(x, y, direction).is_some())
+        {
+            arms[direction] = weight;
+        }
+        self.set(x, y, line_char(arms, weight));
+    }
+
+    /// Whether the neighbour towards `direction` has an arm reaching (`x`, `y`)
+    fn reaches_back(&self, x: usize, y: usize, direction: usize) -> bool {
+        self.neighbour(x, y, direction)
+            .and_then(|(nx, ny)| box_arms(self.cell(nx, ny)))
+            .is_some_and(|arms| arms[opposite(direction)] != Weight::None)
+    }
+
+    /// Clear the selected cell, pulling back the arms of lines that reached it
+    pub fn erase(&mut self) {
+        let (x, y) = self.cursor;
+        let was_line = box_arms(self.cell(x, y)).is_some();
+        self.set(x, y, ' ');
+        if !was_line {
+            return;
+        }
+        for direction in [LEFT, RIGHT, UP, DOWN] {
+            let Some((nx, ny)) = self.neighbour(x, y, direction) else {
+                continue;
+            };
+            let Some(mut their_arms) = box_arms(self.cell(nx, ny)) else {
+                continue;
+            };
+            let weight = line_weight(their_arms);
+            their_arms[opposite(direction)] = Weight::None;
+            if their_arms.iter().any(|&arm| arm != Weight::None) {
+                self.set(nx, ny, line_char(their_arms, weight));
+            }
+        }
+    }
+
+    /// Redraw the glyph's drop shadow from its body
+    pub fn generate_shadow(&mut self) {
+        let data = transform::shadow(&self.glyph().data);
+        let width = data.first().map_or(0, |row| row.chars().count()) as u32;
+        let ch = self.current();
+        let glyph = self
+            .font
+            .characters
+            .get_mut(&ch)
+            .expect("current glyph exists");
+        glyph.data = data;
+        glyph.width = width;
+        self.modified = true;
+    }
+
+    /// Add or remove columns on the right of the glyph
+    pub fn resize(&mut self, delta: i32) {
+        let ch = self.current();
+        let glyph = self
+            .font
+            .characters
+            .get_mut(&ch)
+            .expect("current glyph exists");
+        let width = (glyph.width as i32 + delta).max(1) as usize;
+        for row in &mut glyph.data {
+            *row = row
+                .chars()
+                .chain(std::iter::repeat(' '))
+                .take(width)
+                .collect();
+        }
+        glyph.width = width as u32;
+        self.cursor.0 = self.cursor.0.min(width - 1);
+        self.modified = true;
+    }
+
+    /// Edit the glyph for `ch`, adding a blank one if the font does not have
+    /// it yet, as wide as the fallback glyph it was drawn with until now
+    pub fn select(&mut self, ch: char) {
+        if !self.font.characters.contains_key(&ch) {
+            let width = self
+                .font
+                .get_character(ch)
+                .map_or(self.glyph().width, |glyph| glyph.width);
+            let height = self.font.height;
+            let data = vec![" ".repeat(width as usize); height as usize];
+            self.font.characters.insert(
+                ch,
+                FontCharacter {
+                    width,
+                    height,
+                    data,
+                },
+            );
+            self.chars.push(ch);
+            self.chars.sort_unstable();
+            self.modified = true;
+        }
+        self.index = self.chars.binary_search(&ch).expect("glyph was just added");
+        self.cursor.0 = self
+            .cursor
+            .0
+            .min((self.glyph().width as usize).saturating_sub(1));
+    }
+
+    fn step_glyph(&mut self, forward: bool) {
+        let count = self.chars.len();
+        self.index = if forward {
+            (self.index + 1) % count
+        } else {
+            (self.index + count - 1) % count
+        };
+        self.cursor.0 = self
+            .cursor
+            .0
+            .min((self.glyph().width as usize).saturating_sub(1));
+    }
+
+    fn move_cursor(&mut self, direction: usize) {
+        let (x, y) = self.cursor;
+        if let Some(cell) = self.neighbour(x, y, direction) {
+            self.cursor = cell;
+        }
+    }
+
+    /// Write the font back to its file
+    pub fn save(&mut self) -> io::Result<()> {
+        fs::write(&self.path, fontfile::write(&self.font))?;
+        self.modified = false;
+        Ok(())
+    }
+
+    /// Act on a key press, returning `false` when the editor should close
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if let Some(prompt) = &mut self.prompt {
+            let input = match prompt {
+                Prompt::Glyph(input) | Prompt::Preview(input) => input,
+            };
+            match key.code {
+                KeyCode::Char(c) => input.push(c),
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Enter => match self.prompt.take() {
+                    Some(Prompt::Glyph(input)) => match fontfile::parse_character(input.trim()) {
+                        Ok(ch) if !ch.is_control() => self.select(ch),
+                        _ => {
+                            self.message = format!(
+                                "'{}' is not a single character or U+XXXX code point",
+                                input
+                            )
+                        }
+                    },
+                    Some(Prompt::Preview(input)) => self.preview = input,
+                    None => {}
+                },
+                KeyCode::Esc => self.prompt = None,
+                _ => {}
+            }
+            return true;
+        }
+
+        let quitting = matches!(key.code, KeyCode::Char('q') | KeyCode::Esc)
+            || (key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL));
+        if !quitting {
+            self.confirm_quit = false;
+        }
+        self.message.clear();
+
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        let direction = match key.code {
+            KeyCode::Left => Some(LEFT),
+            KeyCode::Right => Some(RIGHT),
+            KeyCode::Up => Some(UP),
+            KeyCode::Down => Some(DOWN),
+            _ => None,
+        };
+        if let Some(direction) = direction {
+            // Shift drags the brush, painting both ends of the step so lines join
+            if shift {
+                self.paint(Some(direction));
+            }
+            self.move_cursor(direction);
+            if shift {
+                self.paint(Some(opposite(direction)));
+            }
+            return true;
+        }
+
+        match key.code {
+            KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.save_with_message()
+            }
+            KeyCode::Char(' ') | KeyCode::Enter => self.paint(None),
+            KeyCode::Char('x') | KeyCode::Backspace | KeyCode::Delete => self.erase(),
+            KeyCode::Tab | KeyCode::PageDown => self.step_glyph(true),
+            KeyCode::BackTab | KeyCode::PageUp => self.step_glyph(false),
+            KeyCode::Char(digit @ '1'..='5') => {
+                self.brush = Brush::ALL[digit as usize - '1' as usize]
+            }
+            KeyCode::Char('b') => {
+                let next = Brush::ALL
+                    .iter()
+                    .position(|&brush| brush == self.brush)
+                    .unwrap_or(0)
+                    + 1;
+                self.brush = Brush::ALL[next % Brush::ALL.len()];
+            }
+            KeyCode::Char('[') => self.resize(-1),
+            KeyCode::Char(']') => self.resize(1),
+            KeyCode::Char('s') => self.generate_shadow(),
+            KeyCode::Char('g') => self.prompt = Some(Prompt::Glyph(String::new())),
+            KeyCode::Char('p') => self.prompt = Some(Prompt::Preview(self.preview.clone())),
+            KeyCode::Char('w') => self.save_with_message(),
+            _ if quitting => {
+                if !self.modified || self.confirm_quit {
+                    return false;
+                }
+                self.confirm_quit = true;
+                self.message = "Unsaved changes, press q again to quit without saving".to_string();
+            }
+            _ => {}
+        }
+        true
+    }
+
+    fn save_with_message(&mut self) {
+        self.message = match self.save() {
+            Ok(()) => format!("Saved {}", self.path.display()),
+            Err(e) => format!("Failed to save {}: {}", self.path.display(), e),
+        };
+    }
+
+    /// The screen as lines of text, with the column of the selected cell on
+    /// the line it is on
+    fn screen(&self, columns: usize) -> (Vec<String>, (usize, usize)) {
+        let ch = self.current();
+        let glyph = self.glyph();
+        let (width, height) = self.size();
+        let mut lines = vec![
+            format!(
+                "blocklet font edit  {}{}",
+                self.path.display(),
+                if self.modified { " (modified)" } else { "" }
+            ),
+            format!(
+                "Glyph {} U+{:04X} ({}/{})  width {}  brush {} {}",
+                display_char(ch),
+                ch as u32,
+                self.index + 1,
+                self.chars.len(),
+                width,
+                self.brush.symbol(),
+                self.brush.name()
+            ),
+            String::new(),
+            format!("┌{}┐", "─".repeat(width)),
+        ];
+        let grid_top = lines.len();
+        // Blank cells show as dots so the extent of the glyph is visible
+        lines.extend(
+            glyph
+                .data
+                .iter()
+                .map(|row| format!("│{}│", row.replace(' ', "·"))),
+        );
+        lines.push(format!("└{}┘", "─".repeat(width)));
+        let cursor = (self.cursor.0 + 1, grid_top + self.cursor.1.min(height - 1));
+
+        lines.push(String::new());
+        let strip: Vec<String> = self
+            .chars
+            .iter()
+            .map(|&c| {
+                if c == ch {
+                    format!("[{}]", display_char(c))
+                } else {
+                    display_char(c).to_string()
+                }
+            })
+            .collect();
+        lines.push(format!("Glyphs: {}", strip.join(" ")));
+
+        lines.push(String::new());
+        lines.push(format!("Preview: {}", self.preview));
+        let options = RenderOptions {
+            font_name: self.font.name.clone(),
+            max_width: columns as u32,
+            height: self.font.height,
+            spacing: 1,
+            ..RenderOptions::default()
+        };
+        match Renderer::with_font(&self.font, options).render(&self.preview) {
+            Ok(art) => lines.extend(art.lines().map(str::to_string)),
+            Err(e) => lines.push(e.to_string()),
+        }
+
+        lines.push(String::new());
+        lines.push(match &self.prompt {
+            Some(Prompt::Glyph(input)) => {
+                format!("Go to or add glyph (character or U+XXXX): {}", input)
+            }
+            Some(Prompt::Preview(input)) => format!("Preview text: {}", input),
+            None => self.message.clone(),
+        });
+        lines.push(HELP.to_string());
+        (lines, cursor)
+    }
+
+    fn draw<W: Write>(&self, out: &mut W, (columns, rows): (u16, u16)) -> io::Result<()> {
+        let (lines, (cursor_x, cursor_y)) = self.screen(columns as usize);
+        queue!(out, terminal::Clear(terminal::ClearType::All))?;
+        for (y, line) in lines.iter().take(rows as usize).enumerate() {
+            queue!(out, cursor::MoveTo(0, y as u16))?;
+            let line: Vec<char> = line.chars().take(columns as usize).collect();
+            if y != cursor_y {
+                queue!(out, Print(line.iter().collect::<String>()))?;
+                continue;
+            }
+            for (x, &c) in line.iter().enumerate() {
+                if x == cursor_x {
+                    queue!(
+                        out,
+                        SetAttribute(Attribute::Reverse),
+                        Print(c),
+                        SetAttribute(Attribute::NoReverse)
+                    )?;
+                } else {
+                    queue!(out, Print(c))?;
+                }
+            }
+        }
+        out.flush()
+    }
+}
+
+/// Run the editor in the terminal until it is closed
+pub fn run(editor: &mut Editor) -> io::Result<()> {
+    let mut out = io::stdout();
+    let _screen = Screen::enter(&mut out)?;
+    event_loop(editor, &mut out)
+}
+
+/// Raw mode and the alternate screen, left again when dropped so the
+/// terminal is restored however the editor exits, including by a panic
+struct Screen;
+
+impl Screen {
+    fn enter<W: Write>(out: &mut W) -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        let screen = Screen;
+        queue!(out, terminal::EnterAlternateScreen, cursor::Hide)?;
+        out.flush()?;
+        Ok(screen)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let mut out = io::stdout();
+        // Nothing more can be done if the terminal cannot be restored
+        let _ = queue!(out, cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = out.flush();
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+fn event_loop<W: Write>(editor: &mut Editor, out: &mut W) -> io::Result<()> {
+    loop {
+        editor.draw(out, terminal::size()?)?;
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press && !editor.handle_key(key) {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// The box-drawing character with `arms`, extending a lone arm across the
+/// cell since double lines have no half strokes
+fn line_char(mut arms: Arms, weight: Weight) -> char {
+    let set: Vec<usize> = (0..4)
+        .filter(|&direction| arms[direction] != Weight::None)
+        .collect();
+    match set[..] {
+        [] => return stroke(weight, true),
+        [direction] => arms[opposite(direction)] = weight,
+        _ => {}
+    }
+    from_arms(arms).unwrap_or_else(|| stroke(weight, arms[UP] == Weight::None))
+}
+
+/// The weight of a box-drawing character's strokes
+fn line_weight(arms: Arms) -> Weight {
+    arms.into_iter()
+        .find(|&arm| arm != Weight::None)
+        .unwrap_or(Weight::Light)
+}
+
+fn opposite(direction: usize) -> usize {
+    match direction {
+        LEFT => RIGHT,
+        RIGHT => LEFT,
+        UP => DOWN,
+        _ => UP,
+    }
+}
+
+fn display_char(ch: char) -> char {
+    if ch == ' ' {
+        '␠'
+    } else {
+        ch
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// A path no other test uses, since tests run in parallel
+    fn temp_path() -> PathBuf {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+        let count = COUNT.fetch_add(1, Ordering::Relaxed);
+        std::env::temp_dir().join(format!(
+            "blocklet-editor-{}-{}.blf",
+            std::process::id(),
+            count
+        ))
+    }
+
+    fn editor(height: u32) -> Editor {
+        let path = temp_path();
+        let mut editor = Editor::new(blank_font("test", height, path.clone()), path);
+        editor.select('A');
+        editor
+    }
+
+    fn press(editor: &mut Editor, keys: &[KeyCode]) {
+        for &code in keys {
+            assert!(editor.handle_key(KeyEvent::new(code, KeyModifiers::NONE)));
+        }
+    }
+
+    fn drag(editor: &mut Editor, code: KeyCode) {
+        editor.handle_key(KeyEvent::new(code, KeyModifiers::SHIFT));
+    }
+
+    fn rows(editor: &Editor) -> Vec<String> {
+        editor.font().characters[&editor.current()].data.clone()
+    }
+
+    #[test]
+    fn test_paint_blocks() {
+        let mut editor = editor(2);
+        press(
+            &mut editor,
+            &[
+                KeyCode::Char(' '),
+                KeyCode::Right,
+                KeyCode::Down,
+                KeyCode::Char('2'),
+                KeyCode::Char(' '),
+            ],
+        );
+        assert_eq!(rows(&editor), ["█ ", " ▀"]);
+        assert!(editor.is_modified());
+    }
+
+    #[test]
+    fn test_lines_join() {
+        let mut editor = editor(3);
+        press(&mut editor, &[KeyCode::Char('4')]);
+        drag(&mut editor, KeyCode::Right);
+        drag(&mut editor, KeyCode::Right);
+        drag(&mut editor, KeyCode::Down);
+        assert_eq!(rows(&editor), ["══╗", "  ║", "   "]);
+
+        // A line painted next to another only joins it when dragged into it
+        press(&mut editor, &[KeyCode::Left, KeyCode::Char(' ')]);
+        assert_eq!(rows(&editor), ["══╗", " ═║", "   "]);
+        drag(&mut editor, KeyCode::Right);
+        assert_eq!(rows(&editor), ["══╗", " ═╝", "   "]);
+
+        // Erasing pulls back the arms that reached the cell
+        press(&mut editor, &[KeyCode::Left, KeyCode::Char('x')]);
+        assert_eq!(rows(&editor), ["══╗", "  ║", "   "]);
+    }
+
+    #[test]
+    fn test_shadow_and_width() {
+        let mut editor = editor(3);
+        editor.resize(-1);
+        assert_eq!(rows(&editor), ["  ", "  ", "  "]);
+        press(
+            &mut editor,
+            &[
+                KeyCode::Char(' '),
+                KeyCode::Down,
+                KeyCode::Char(' '),
+                KeyCode::Char('s'),
+            ],
+        );
+        assert_eq!(rows(&editor), ["█╗", "█║", "╚╝"]);
+
+        // The shadow needs a column of its own when the body fills the glyph
+        press(
+            &mut editor,
+            &[KeyCode::Right, KeyCode::Char(' '), KeyCode::Char('s')],
+        );
+        assert_eq!(rows(&editor), ["█╗ ", "██╗", "╚═╝"]);
+        assert_eq!(editor.font().characters[&'A'].width, 3);
+    }
+
+    #[test]
+    fn test_zero_width_glyph() {
+        let path = temp_path();
+        let text = "name = zero\nheight = 1\nglyph U+0020 1\n| |\nglyph ? 1\n|?|\nglyph X 0\n||\n";
+        let font = fontfile::parse(text, FontSource::BuiltIn).unwrap();
+        let mut editor = Editor::new(font, path);
+        editor.select('X');
+        press(
+            &mut editor,
+            &[
+                KeyCode::Char(' '),
+                KeyCode::Tab,
+                KeyCode::BackTab,
+                KeyCode::Right,
+            ],
+        );
+        assert_eq!(editor.current(), 'X');
+        assert_eq!(editor.font().characters[&'X'].width, 0);
+    }
+
+    #[test]
+    fn test_select_and_save() {
+        let mut editor = editor(2);
+        press(
+            &mut editor,
+            &[
+                KeyCode::Char('g'),
+                KeyCode::Char('U'),
+                KeyCode::Char('+'),
+                KeyCode::Char('0'),
+                KeyCode::Char('0'),
+            ],
+        );
+        press(
+            &mut editor,
+            &[KeyCode::Char('4'), KeyCode::Char('2'), KeyCode::Enter],
+        );
+        assert_eq!(editor.current(), 'B');
+        assert_eq!(editor.font().characters.len(), 4);
+
+        // Quitting with unsaved changes needs a second press
+        assert!(editor.handle_key(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE)));
+        press(&mut editor, &[KeyCode::Char('w')]);
+        assert!(!editor.is_modified());
+        assert!(!editor.handle_key(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE)));
+
+        let text = fs::read_to_string(&editor.path).unwrap();
+        fs::remove_file(&editor.path).unwrap();
+        let font = fontfile::parse(&text, FontSource::BuiltIn).unwrap();
+        assert!(font.characters.contains_key(&'B'));
+    }
+}
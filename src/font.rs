@@ -1,4 +1,7 @@
 use std::collections::HashMap;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -22,17 +25,62 @@ pub struct Font {
     pub description: String,
     pub height: u32,
     pub characters: HashMap<char, FontCharacter>,
+    /// Where the font was loaded from
+    pub source: FontSource,
 }
 
+/// Where a font comes from
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FontSource {
+    /// Compiled into blocklet
+    BuiltIn,
+    /// Loaded from a file
+    File(PathBuf),
+}
+
+impl fmt::Display for FontSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FontSource::BuiltIn => f.write_str("built-in"),
+            FontSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// Glyph drawn in place of characters a font does not have
+pub const FALLBACK_CHARACTER: char = '?';
+
 impl Font {
     pub fn get_character(&self, ch: char) -> Result<&FontCharacter, FontError> {
-        // Convert to uppercase for consistency
-        let ch = ch.to_ascii_uppercase();
+        // Fonts without lowercase letters draw them as uppercase
+        let upper = ch.to_ascii_uppercase();
         
         self.characters
             .get(&ch)
-            .or_else(|| self.characters.get(&'?')) // Fallback to '?' for unknown characters
-            .ok_or_else(|| FontError::CharacterNotSupported(ch, self.name.clone()))
+            .or_else(|| self.characters.get(&upper))
+            .or_else(|| self.characters.get(&FALLBACK_CHARACTER)) // Fallback to '?' for unknown characters
+            .ok_or_else(|| FontError::CharacterNotSupported(upper, self.name.clone()))
+    }
+
+    /// Whether the font has its own glyph for `ch`, rather than the fallback
+    pub fn has_character(&self, ch: char) -> bool {
+        self.characters.contains_key(&ch) || self.characters.contains_key(&ch.to_ascii_uppercase())
+    }
+
+    /// The characters the font has glyphs for, as sorted runs of
+    /// consecutive code points
+    pub fn coverage(&self) -> Vec<RangeInclusive<char>> {
+        let mut chars: Vec<char> = self.characters.keys().copied().collect();
+        chars.sort_unstable();
+
+        let mut runs: Vec<RangeInclusive<char>> = Vec::new();
+        for ch in chars {
+            match runs.last_mut() {
+                Some(run) if *run.end() as u32 + 1 == ch as u32 => *run = *run.start()..=ch,
+                _ => runs.push(ch..=ch),
+            }
+        }
+        runs
     }
 }
 
@@ -658,6 +706,7 @@ fn create_standard_shadow_font() -> Font {
         description: "Standard Unicode box drawing font with built-in shadows and descenders".to_string(),
         height: 7,
         characters,
+        source: FontSource::BuiltIn,
     }
 }
 
@@ -695,6 +744,7 @@ fn create_standard_solid_font() -> Font {
         description: "Standard solid block font without shadows (converted from shadow font)".to_string(),
         height: 7,
         characters,
+        source: FontSource::BuiltIn,
     }
 }
 
@@ -714,7 +764,57 @@ lazy_static::lazy_static! {
     };
 }
 
-pub fn get_font(name: &str) -> Result<&Font, FontError> {
+/// Names of all built-in fonts, sorted alphabetically
+pub fn font_names() -> Vec<&'static str> {
+    let mut names: Vec<&'static str> = FONTS.keys().map(|name| name.as_str()).collect();
+    names.sort_unstable();
+    names
+}
+
+/// Every registered font with the name it is registered under, sorted by
+/// name. A font registered under another name than its own is an alias.
+pub fn fonts() -> Vec<(&'static str, &'static Font)> {
+    font_names().into_iter().map(|name| (name, &FONTS[name])).collect()
+}
+
+pub fn get_font(name: &str) -> Result<&'static Font, FontError> {
     FONTS.get(name).ok_or_else(|| FontError::FontNotFound(name.to_string()))
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coverage_runs() {
+        let font = get_font("standard_shadow").unwrap();
+        let coverage = font.coverage();
+        assert!(coverage.contains(&('A'..='Z')));
+        assert_eq!(coverage.iter().map(|run| run.clone().count()).sum::<usize>(), font.characters.len());
+    }
+
+    #[test]
+    fn test_lowercase_falls_back_to_uppercase() {
+        let glyph = |row: &str| FontCharacter { width: 1, height: 1, data: vec![row.to_string()] };
+        let font = Font {
+            name: "test".to_string(),
+            description: String::new(),
+            height: 1,
+            characters: [('A', glyph("A")), ('B', glyph("B")), ('b', glyph("b"))].into_iter().collect(),
+            source: FontSource::BuiltIn,
+        };
+        assert_eq!(font.get_character('a').unwrap().data, ["A"]);
+        assert_eq!(font.get_character('b').unwrap().data, ["b"]);
+        assert!(font.has_character('a') && font.has_character('b'));
+    }
+
+    #[test]
+    fn test_registry() {
+        let fonts = fonts();
+        assert_eq!(fonts.len(), font_names().len());
+        assert!(fonts.iter().all(|(_, font)| font.source == FontSource::BuiltIn));
+        let (name, standard) = fonts[0];
+        assert_eq!(name, "standard");
+        assert_eq!(standard.name, "standard_shadow");
+    }
+}
@@ -0,0 +1,481 @@
+//! Loading fonts from `.blf` text files.
+//!
+//! A font file starts with `key = value` settings and then lists its glyphs.
+//! Each glyph is a `glyph <character> <width>` header followed by one row per
+//! line of the font's height, written between `|` delimiters so that leading
+//! and trailing spaces survive editors. The character is written as itself or
+//! as a `U+XXXX` code point, which is required for the space. Blank lines and
+//! lines starting with `#` are ignored.
+//!
+//! ```text
+//! # A two row font
+//! name = tiny
+//! description = Two rows tall
+//! height = 2
+//!
+//! glyph U+0020 2
+//! |  |
+//! |  |
+//!
+//! glyph ? 2
+//! |▀█|
+//! | ▄|
+//! ```
+//!
+//! Every font needs a space and the `?` fallback glyph, rows must match the
+//! font's height and the glyph's declared width, and glyphs may not contain
+//! control characters or appear twice.
+
+use crate::font::{Font, FontCharacter, FontSource, FALLBACK_CHARACTER};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use thiserror::Error;
+
+/// A problem in a font file and the 1-based line it is on, if it belongs to one
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FontFileError {
+    pub line: Option<usize>,
+    pub kind: ErrorKind,
+}
+
+impl fmt::Display for FontFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.kind),
+            None => write!(f, "{}", self.kind),
+        }
+    }
+}
+
+impl std::error::Error for FontFileError {}
+
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum ErrorKind {
+    #[error("expected `key = value`, `glyph <character> <width>` or a `|row|`")]
+    Syntax,
+    #[error("unknown setting '{0}'")]
+    UnknownKey(String),
+    #[error("'{0}' is set more than once")]
+    DuplicateKey(String),
+    #[error("missing '{0}' setting")]
+    MissingKey(&'static str),
+    #[error("'{value}' is not a valid {what}")]
+    InvalidNumber { what: &'static str, value: String },
+    #[error("'{0}' is not a single character or U+XXXX code point")]
+    InvalidCharacter(String),
+    #[error("row is not closed with '|'")]
+    UnterminatedRow,
+    #[error("row before the first glyph header")]
+    RowOutsideGlyph,
+    #[error("control character U+{:04X} in column {column}", *.ch as u32)]
+    ControlCharacter { ch: char, column: usize },
+    #[error("glyph for control character U+{:04X}", *.0 as u32)]
+    ControlGlyph(char),
+    #[error("glyph '{ch}' is defined again (first on line {first_line})")]
+    DuplicateGlyph { ch: char, first_line: usize },
+    #[error("glyph '{ch}' has {rows} rows but the font is {height} rows tall")]
+    RowCount { ch: char, rows: usize, height: u32 },
+    #[error("row is {width} columns wide but glyph '{ch}' declares {declared}")]
+    RowWidth {
+        ch: char,
+        width: usize,
+        declared: u32,
+    },
+    #[error("missing the {0} glyph")]
+    MissingGlyph(&'static str),
+}
+
+impl ErrorKind {
+    fn at(self, line: usize) -> FontFileError {
+        FontFileError {
+            line: Some(line),
+            kind: self,
+        }
+    }
+}
+
+impl From<ErrorKind> for FontFileError {
+    fn from(kind: ErrorKind) -> Self {
+        FontFileError { line: None, kind }
+    }
+}
+
+/// Why a font file could not be loaded
+#[derive(Debug, Error)]
+pub enum LoadError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("{}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n"))]
+    Invalid(Vec<FontFileError>),
+}
+
+/// A glyph as written in the file, before it is checked against the font
+struct GlyphEntry {
+    ch: char,
+    width: u32,
+    line: usize,
+    rows: Vec<(usize, String)>,
+}
+
+/// Parse a font file, reporting every problem in it rather than only the
+/// first, ordered by line with problems that belong to no line last
+pub fn parse(text: &str, source: FontSource) -> Result<Font, Vec<FontFileError>> {
+    let mut errors = Vec::new();
+    let mut settings: HashMap<&str, (usize, &str)> = HashMap::new();
+    let mut glyphs: Vec<GlyphEntry> = Vec::new();
+
+    for (index, line) in text.lines().enumerate() {
+        let number = index + 1;
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        if let Some(row) = trimmed.strip_prefix('|') {
+            let Some(row) = row.strip_suffix('|') else {
+                errors.push(ErrorKind::UnterminatedRow.at(number));
+                continue;
+            };
+            if let Some((column, ch)) = row.chars().enumerate().find(|(_, ch)| ch.is_control()) {
+                errors.push(
+                    ErrorKind::ControlCharacter {
+                        ch,
+                        column: column + 2,
+                    }
+                    .at(number),
+                );
+            }
+            match glyphs.last_mut() {
+                Some(glyph) => glyph.rows.push((number, row.to_string())),
+                None => errors.push(ErrorKind::RowOutsideGlyph.at(number)),
+            }
+        } else if let Some(header) = trimmed.strip_prefix("glyph ") {
+            match parse_glyph_header(header) {
+                Ok((ch, width)) => {
+                    if ch.is_control() {
+                        errors.push(ErrorKind::ControlGlyph(ch).at(number));
+                    }
+                    if let Some(first) = glyphs.iter().find(|glyph| glyph.ch == ch) {
+                        errors.push(
+                            ErrorKind::DuplicateGlyph {
+                                ch,
+                                first_line: first.line,
+                            }
+                            .at(number),
+                        );
+                    }
+                    glyphs.push(GlyphEntry {
+                        ch,
+                        width,
+                        line: number,
+                        rows: Vec::new(),
+                    });
+                }
+                Err(kind) => errors.push(kind.at(number)),
+            }
+        } else if let Some((key, value)) = trimmed.split_once('=') {
+            let (key, value) = (key.trim(), value.trim());
+            if !matches!(key, "name" | "description" | "height") {
+                errors.push(ErrorKind::UnknownKey(key.to_string()).at(number));
+            } else if settings.insert(key, (number, value)).is_some() {
+                errors.push(ErrorKind::DuplicateKey(key.to_string()).at(number));
+            }
+        } else {
+            errors.push(ErrorKind::Syntax.at(number));
+        }
+    }
+
+    let name = settings.get("name").map(|&(_, value)| value.to_string());
+    if name.is_none() {
+        errors.push(ErrorKind::MissingKey("name").into());
+    }
+    let height = match settings.get("height") {
+        Some(&(line, value)) => match value.parse::<u32>() {
+            Ok(height) if height > 0 => Some(height),
+            _ => {
+                let what = "height";
+                errors.push(
+                    ErrorKind::InvalidNumber {
+                        what,
+                        value: value.to_string(),
+                    }
+                    .at(line),
+                );
+                None
+            }
+        },
+        None => {
+            errors.push(ErrorKind::MissingKey("height").into());
+            None
+        }
+    };
+
+    for glyph in &glyphs {
+        if let Some(height) = height {
+            if glyph.rows.len() != height as usize {
+                let kind = ErrorKind::RowCount {
+                    ch: glyph.ch,
+                    rows: glyph.rows.len(),
+                    height,
+                };
+                errors.push(kind.at(glyph.line));
+            }
+        }
+        for (line, row) in &glyph.rows {
+            let width = row.chars().count();
+            if width != glyph.width as usize {
+                errors.push(
+                    ErrorKind::RowWidth {
+                        ch: glyph.ch,
+                        width,
+                        declared: glyph.width,
+                    }
+                    .at(*line),
+                );
+            }
+        }
+    }
+    for (ch, what) in [(' ', "space"), (FALLBACK_CHARACTER, "'?' fallback")] {
+        if !glyphs.iter().any(|glyph| glyph.ch == ch) {
+            errors.push(ErrorKind::MissingGlyph(what).into());
+        }
+    }
+
+    if !errors.is_empty() {
+        errors.sort_by_key(|error| error.line.unwrap_or(usize::MAX));
+        return Err(errors);
+    }
+    let (Some(name), Some(height)) = (name, height) else {
+        unreachable!("missing settings are reported as errors");
+    };
+
+    let characters = glyphs
+        .into_iter()
+        .map(|glyph| {
+            let data = glyph.rows.into_iter().map(|(_, row)| row).collect();
+            (
+                glyph.ch,
+                FontCharacter {
+                    width: glyph.width,
+                    height,
+                    data,
+                },
+            )
+        })
+        .collect();
+    Ok(Font {
+        name,
+        description: settings
+            .get("description")
+            .map_or(String::new(), |&(_, value)| value.to_string()),
+        height,
+        characters,
+        source,
+    })
+}
+
+/// Every problem in a font file, or nothing when it loads
+pub fn check(text: &str) -> Vec<FontFileError> {
+    parse(text, FontSource::BuiltIn).err().unwrap_or_default()
+}
+
+/// Load a font file from disk
+pub fn load(path: &Path) -> Result<Font, LoadError> {
+    let text = fs::read_to_string(path)?;
+    parse(&text, FontSource::File(path.to_path_buf())).map_err(LoadError::Invalid)
+}
+
+/// Write a font in the font file format, with its glyphs ordered by code point
+pub fn write(font: &Font) -> String {
+    let mut out = format!("name = {}\n", font.name);
+    if !font.description.is_empty() {
+        out.push_str(&format!("description = {}\n", font.description));
+    }
+    out.push_str(&format!("height = {}\n", font.height));
+
+    let mut chars: Vec<char> = font.characters.keys().copied().collect();
+    chars.sort_unstable();
+    for ch in chars {
+        let glyph = &font.characters[&ch];
+        if ch.is_whitespace() || ch.is_control() {
+            out.push_str(&format!("\nglyph U+{:04X} {}\n", ch as u32, glyph.width));
+        } else {
+            out.push_str(&format!("\nglyph {} {}\n", ch, glyph.width));
+        }
+        for row in &glyph.data {
+            out.push_str(&format!("|{}|\n", row));
+        }
+    }
+    out
+}
+
+/// Parse the `<character> <width>` after `glyph`
+fn parse_glyph_header(header: &str) -> Result<(char, u32), ErrorKind> {
+    let mut parts = header.split_whitespace();
+    let (Some(ch), Some(width), None) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(ErrorKind::Syntax);
+    };
+    let ch = parse_character(ch)?;
+    let width = width.parse().map_err(|_| ErrorKind::InvalidNumber {
+        what: "width",
+        value: width.to_string(),
+    })?;
+    Ok((ch, width))
+}
+
+/// A character written as itself or as `U+XXXX`
+pub(crate) fn parse_character(text: &str) -> Result<char, ErrorKind> {
+    let invalid = || ErrorKind::InvalidCharacter(text.to_string());
+    if let Some(hex) = text.strip_prefix("U+") {
+        return u32::from_str_radix(hex, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(invalid);
+    }
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(ch), None) => Ok(ch),
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TINY: &str = "# A two row font
+name = tiny
+description = Two rows tall
+height = 2
+
+glyph U+0020 2
+|  |
+|  |
+
+glyph ? 2
+|▀█|
+| ▄|
+
+glyph A 3
+|▄▀▄|
+|█▀█|
+";
+
+    #[test]
+    fn test_parse() {
+        let font = parse(TINY, FontSource::BuiltIn).unwrap();
+        assert_eq!(font.name, "tiny");
+        assert_eq!(font.description, "Two rows tall");
+        assert_eq!(font.height, 2);
+        assert_eq!(font.characters.len(), 3);
+        assert_eq!(font.characters[&' '].data, ["  ", "  "]);
+        assert_eq!(font.characters[&'A'].width, 3);
+        assert!(check(TINY).is_empty());
+    }
+
+    #[test]
+    fn test_write_round_trip() {
+        let font = parse(TINY, FontSource::BuiltIn).unwrap();
+        let text = write(&font);
+        assert!(text.starts_with(
+            "name = tiny\ndescription = Two rows tall\nheight = 2\n\nglyph U+0020 2\n|  |\n"
+        ));
+        let reloaded = parse(&text, FontSource::BuiltIn).unwrap();
+        assert_eq!(reloaded.characters.len(), font.characters.len());
+        assert!(font
+            .characters
+            .iter()
+            .all(|(ch, glyph)| reloaded.characters[ch].data == glyph.data));
+    }
+
+    #[test]
+    fn test_check_reports_lines() {
+        let text = TINY
+            .replace("height = 2", "height = 2\ncolour = red")
+            .replace("| ▄|", "| ▄\u{7}|")
+            .replace("|█▀█|\n", "|█▀█|\n|█ █ |\n")
+            + "glyph A 1\n|x|\n|y|\n";
+        let errors = check(&text);
+        let lines: Vec<_> = errors
+            .iter()
+            .map(|error| (error.line, error.kind.clone()))
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                (Some(5), ErrorKind::UnknownKey("colour".to_string())),
+                (
+                    Some(13),
+                    ErrorKind::ControlCharacter {
+                        ch: '\u{7}',
+                        column: 4
+                    }
+                ),
+                (
+                    Some(13),
+                    ErrorKind::RowWidth {
+                        ch: '?',
+                        width: 3,
+                        declared: 2
+                    }
+                ),
+                (
+                    Some(15),
+                    ErrorKind::RowCount {
+                        ch: 'A',
+                        rows: 3,
+                        height: 2
+                    }
+                ),
+                (
+                    Some(18),
+                    ErrorKind::RowWidth {
+                        ch: 'A',
+                        width: 4,
+                        declared: 3
+                    }
+                ),
+                (
+                    Some(19),
+                    ErrorKind::DuplicateGlyph {
+                        ch: 'A',
+                        first_line: 15
+                    }
+                ),
+            ]
+        );
+        assert_eq!(
+            errors[3].to_string(),
+            "line 15: glyph 'A' has 3 rows but the font is 2 rows tall"
+        );
+    }
+
+    #[test]
+    fn test_check_missing_glyphs_and_settings() {
+        let errors = check("glyph A 1\n|x|\n");
+        let kinds: Vec<_> = errors
+            .iter()
+            .map(|error| (error.line, error.kind.clone()))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                (None, ErrorKind::MissingKey("name")),
+                (None, ErrorKind::MissingKey("height")),
+                (None, ErrorKind::MissingGlyph("space")),
+                (None, ErrorKind::MissingGlyph("'?' fallback")),
+            ]
+        );
+        assert_eq!(
+            check("name = x\nheight = 1\n|x|")[0].kind,
+            ErrorKind::RowOutsideGlyph
+        );
+        assert_eq!(
+            check("name = x\nheight = 1\nglyph AB 1")[0].kind,
+            ErrorKind::InvalidCharacter("AB".to_string())
+        );
+    }
+}
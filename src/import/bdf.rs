@@ -0,0 +1,357 @@
+//! X11 Glyph Bitmap Distribution Format (BDF) fonts, the text format of
+//! fonts such as Terminus and GNU Unifont.
+//!
+//! Glyph encodings are taken to be Unicode code points, which holds for
+//! `ISO10646` fonts and for the Latin-1 range of `ISO8859-1` fonts.
+
+use super::{Bitmap, ImportError};
+
+/// The most pixels a glyph may be wide or tall, and the font tall. Unifont
+/// glyphs are 16 pixels, so only a broken or hostile file gets near it.
+const MAX_SIZE: i32 = 1024;
+
+/// The glyphs of a BDF font, each placed in a cell as tall as the font
+#[derive(Clone, Debug)]
+pub struct BdfFont {
+    /// The `FONT` name, or the family name when the font has one
+    pub name: String,
+    /// Pixels above the baseline
+    pub ascent: usize,
+    /// Pixels below the baseline
+    pub descent: usize,
+    pub glyphs: Vec<(char, Bitmap)>,
+}
+
+impl BdfFont {
+    pub fn height(&self) -> usize {
+        self.ascent + self.descent
+    }
+}
+
+/// Whether `data` starts like a BDF font
+pub fn is_bdf(data: &[u8]) -> bool {
+    data.starts_with(b"STARTFONT")
+}
+
+/// A glyph as written in the file: its bitmap relative to its bounding box
+struct Glyph {
+    /// The line of its `STARTCHAR`
+    line: usize,
+    code: Option<char>,
+    advance: Option<i32>,
+    bbx: Option<[i32; 4]>,
+    rows: Vec<Vec<u8>>,
+}
+
+pub fn parse(data: &[u8]) -> Result<BdfFont, ImportError> {
+    let text = String::from_utf8_lossy(data);
+    let mut name = String::new();
+    let mut family = None;
+    let mut bounding_box = None;
+    let (mut ascent, mut descent) = (None, None);
+    let mut glyphs = Vec::new();
+
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()));
+    while let Some((number, line)) = lines.next() {
+        let (keyword, args) = line
+            .split_once(' ')
+            .map_or((line, ""), |(keyword, args)| (keyword, args.trim()));
+        match keyword {
+            "FONT" => name = args.to_string(),
+            "FAMILY_NAME" => family = Some(args.trim_matches('"').to_string()),
+            "FONTBOUNDINGBOX" => bounding_box = Some((numbers::<4>(args, number)?, number)),
+            "FONT_ASCENT" => ascent = Some(numbers::<1>(args, number)?[0]),
+            "FONT_DESCENT" => descent = Some(numbers::<1>(args, number)?[0]),
+            "STARTCHAR" => {
+                let glyph = parse_glyph(&mut lines, number)?;
+                if let Some(code) = glyph.code {
+                    glyphs.push((code, glyph));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let ([_, box_height, _, box_y], box_line) =
+        bounding_box.ok_or_else(|| invalid(1, "the font has no FONTBOUNDINGBOX"))?;
+    let out_of_range = || invalid(box_line, "the bounding box is out of range");
+    // Fonts without the ascent and descent properties fit the bounding box
+    let ascent = match ascent {
+        Some(ascent) => ascent,
+        None => box_height.checked_add(box_y).ok_or_else(out_of_range)?,
+    }
+    .max(0) as usize;
+    let descent = match descent {
+        Some(descent) => descent,
+        None => box_y.checked_neg().ok_or_else(out_of_range)?,
+    }
+    .max(0) as usize;
+    let max = MAX_SIZE as usize;
+    if ascent > max || descent > max || ascent + descent > max {
+        return Err(invalid(
+            box_line,
+            &format!("the font is more than {} pixels tall", MAX_SIZE),
+        ));
+    }
+    if ascent + descent == 0 {
+        return Err(invalid(box_line, "the font is no pixels tall"));
+    }
+    let glyphs = glyphs
+        .into_iter()
+        .map(|(code, glyph)| Ok((code, place(&glyph, ascent, descent)?)))
+        .collect::<Result<_, ImportError>>()?;
+
+    Ok(BdfFont {
+        name: family.unwrap_or(name),
+        ascent,
+        descent,
+        glyphs,
+    })
+}
+
+/// Read one glyph, from the line after `STARTCHAR` to `ENDCHAR`
+fn parse_glyph<'a>(
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+    start: usize,
+) -> Result<Glyph, ImportError> {
+    let mut glyph = Glyph {
+        line: start,
+        code: None,
+        advance: None,
+        bbx: None,
+        rows: Vec::new(),
+    };
+    let mut in_bitmap = false;
+    for (number, line) in lines.by_ref() {
+        if line == "ENDCHAR" {
+            return Ok(glyph);
+        }
+        if in_bitmap && (line.starts_with("STARTCHAR") || line == "ENDFONT") {
+            break;
+        }
+        if in_bitmap {
+            let row = (0..line.len() / 2)
+                .map(|i| u8::from_str_radix(line.get(i * 2..i * 2 + 2).unwrap_or(""), 16))
+                .collect::<Result<Vec<u8>, _>>()
+                .map_err(|_| invalid(number, "bitmap row is not hexadecimal"))?;
+            glyph.rows.push(row);
+            continue;
+        }
+        let (keyword, args) = line
+            .split_once(' ')
+            .map_or((line, ""), |(keyword, args)| (keyword, args.trim()));
+        match keyword {
+            // Unencoded glyphs are written as `-1` and have no character
+            "ENCODING" => {
+                let code = args
+                    .split_whitespace()
+                    .next()
+                    .and_then(|code| code.parse::<i64>().ok());
+                let code = code.ok_or_else(|| invalid(number, "ENCODING is not a number"))?;
+                glyph.code = u32::try_from(code).ok().and_then(char::from_u32);
+            }
+            "DWIDTH" => glyph.advance = Some(numbers::<2>(args, number)?[0]),
+            "BBX" => {
+                let bbx = numbers::<4>(args, number)?;
+                if !bbx[..2].iter().all(|size| (0..=MAX_SIZE).contains(size)) {
+                    return Err(invalid(
+                        number,
+                        &format!("glyphs must be 0 to {} pixels wide and tall", MAX_SIZE),
+                    ));
+                }
+                glyph.bbx = Some(bbx);
+            }
+            "BITMAP" => in_bitmap = true,
+            _ => {}
+        }
+    }
+    Err(invalid(start, "STARTCHAR without ENDCHAR"))
+}
+
+/// Draw a glyph's bitmap into a cell as wide as it advances and as tall as
+/// the font, with the baseline `ascent` pixels from the top. Pixels outside
+/// the cell are clipped.
+fn place(glyph: &Glyph, ascent: usize, descent: usize) -> Result<Bitmap, ImportError> {
+    let out_of_range = || invalid(glyph.line, "the glyph's position is out of range");
+    let [width, height, x_offset, y_offset] = glyph.bbx.unwrap_or([0, 0, 0, 0]);
+    let right = x_offset.checked_add(width).ok_or_else(out_of_range)?;
+    let cell_width = glyph.advance.unwrap_or(width).max(right).max(0) as usize;
+    if cell_width > MAX_SIZE as usize {
+        return Err(invalid(
+            glyph.line,
+            &format!("the glyph is more than {} pixels wide", MAX_SIZE),
+        ));
+    }
+    let cell_height = ascent + descent;
+    let mut pixels = vec![false; cell_width * cell_height];
+
+    // Row 0 of the bitmap is the top of the bounding box
+    let top = y_offset
+        .checked_add(height)
+        .and_then(|box_top| (ascent as i32).checked_sub(box_top))
+        .ok_or_else(out_of_range)?;
+    for (row, bytes) in glyph.rows.iter().enumerate().take(height.max(0) as usize) {
+        for column in 0..width.max(0) as usize {
+            let set = bytes
+                .get(column / 8)
+                .is_some_and(|byte| byte & (0x80 >> (column % 8)) != 0);
+            let x = x_offset.checked_add(column as i32);
+            let y = top.checked_add(row as i32);
+            let (Some(x), Some(y)) = (x, y) else {
+                return Err(out_of_range());
+            };
+            if set && (0..cell_width as i32).contains(&x) && (0..cell_height as i32).contains(&y) {
+                pixels[y as usize * cell_width + x as usize] = true;
+            }
+        }
+    }
+    Ok(Bitmap {
+        width: cell_width,
+        height: cell_height,
+        pixels,
+    })
+}
+
+/// Parse exactly `N` whitespace-separated integers
+fn numbers<const N: usize>(args: &str, line: usize) -> Result<[i32; N], ImportError> {
+    let values: Vec<i32> = args
+        .split_whitespace()
+        .map(str::parse)
+        .collect::<Result<_, _>>()
+        .map_err(|_| invalid(line, "expected numbers"))?;
+    values
+        .try_into()
+        .map_err(|_| invalid(line, &format!("expected {} numbers", N)))
+}
+
+fn invalid(line: usize, reason: &str) -> ImportError {
+    ImportError::Bdf {
+        line,
+        reason: reason.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::import::Resolution;
+
+    const FONT: &str = "STARTFONT 2.1
+FONT -misc-tiny-medium-r-normal--6-60-75-75-c-40-iso10646-1
+SIZE 6 75 75
+FONTBOUNDINGBOX 4 6 0 -1
+STARTPROPERTIES 3
+FAMILY_NAME \"Tiny\"
+FONT_ASCENT 5
+FONT_DESCENT 1
+ENDPROPERTIES
+CHARS 3
+STARTCHAR A
+ENCODING 65
+SWIDTH 500 0
+DWIDTH 4 0
+BBX 3 4 0 0
+BITMAP
+40
+A0
+E0
+A0
+ENDCHAR
+STARTCHAR uni4E00
+ENCODING 19968
+DWIDTH 6 0
+BBX 5 1 0 2
+BITMAP
+F8
+ENDCHAR
+STARTCHAR unencoded
+ENCODING -1
+DWIDTH 4 0
+BBX 1 1 0 0
+BITMAP
+80
+ENDCHAR
+ENDFONT
+";
+
+    #[test]
+    fn test_parse() {
+        let font = parse(FONT.as_bytes()).unwrap();
+        assert_eq!(font.name, "Tiny");
+        assert_eq!((font.ascent, font.descent), (5, 1));
+        let chars: Vec<char> = font.glyphs.iter().map(|(ch, _)| *ch).collect();
+        assert_eq!(chars, ['A', '一']);
+
+        // Glyphs sit on the baseline, one pixel above the bottom of the cell
+        let a = &font.glyphs[0].1;
+        assert_eq!((a.width, a.height), (4, 6));
+        assert_eq!(
+            a.to_rows(Resolution::Full),
+            ["    ", " █  ", "█ █ ", "███ ", "█ █ ", "    "]
+        );
+        assert_eq!(
+            font.glyphs[1].1.to_rows(Resolution::Half),
+            ["      ", "▀▀▀▀▀ ", "      "]
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let unterminated = FONT.replace("ENDCHAR\nSTARTCHAR uni4E00", "STARTCHAR uni4E00");
+        assert!(matches!(
+            parse(unterminated.as_bytes()),
+            Err(ImportError::Bdf { line: 11, .. })
+        ));
+        let bad_row = FONT.replace("A0\nE0", "A0\nZZ");
+        assert!(matches!(
+            parse(bad_row.as_bytes()),
+            Err(ImportError::Bdf { line: 19, .. })
+        ));
+        let flat = FONT
+            .replace("FONTBOUNDINGBOX 4 6 0 -1", "FONTBOUNDINGBOX 4 0 0 0")
+            .replace("FONT_ASCENT 5\nFONT_DESCENT 1\n", "");
+        assert!(matches!(
+            parse(flat.as_bytes()),
+            Err(ImportError::Bdf { line: 4, .. })
+        ));
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        let huge = FONT.replace("BBX 3 4 0 0", "BBX 3 4000 0 0");
+        assert!(matches!(
+            parse(huge.as_bytes()),
+            Err(ImportError::Bdf { line: 15, .. })
+        ));
+        let wide = FONT.replace("DWIDTH 4 0", "DWIDTH 40000 0");
+        assert!(matches!(
+            parse(wide.as_bytes()),
+            Err(ImportError::Bdf { line: 11, .. })
+        ));
+        for bbx in ["BBX 3 4 2147483647 0", "BBX 3 4 0 -2147483648"] {
+            let far = FONT.replace("BBX 3 4 0 0", bbx);
+            assert!(matches!(
+                parse(far.as_bytes()),
+                Err(ImportError::Bdf { line: 11, .. })
+            ));
+        }
+        let tall = FONT
+            .replace(
+                "FONTBOUNDINGBOX 4 6 0 -1",
+                "FONTBOUNDINGBOX 4 2147483647 0 1",
+            )
+            .replace("FONT_ASCENT 5\nFONT_DESCENT 1\n", "");
+        assert!(matches!(
+            parse(tall.as_bytes()),
+            Err(ImportError::Bdf { line: 4, .. })
+        ));
+        let deep = FONT.replace("FONT_DESCENT 1", "FONT_DESCENT 2000");
+        assert!(matches!(
+            parse(deep.as_bytes()),
+            Err(ImportError::Bdf { line: 4, .. })
+        ));
+    }
+}
@@ -0,0 +1,392 @@
+//! Importers that turn fonts made for other programs into block fonts.
+//!
+//! Bitmap glyphs are converted cell by cell at a chosen [`Resolution`], and
+//! can be given the same double-line drop shadow as the built-in fonts.
+//! Outline fonts are first rasterised into bitmaps at a chosen size.
+
+pub mod bdf;
+pub mod psf;
+#[cfg(feature = "ttf")]
+pub mod ttf;
+
+use crate::font::{Font, FontCharacter, FontSource, FALLBACK_CHARACTER};
+use crate::transform;
+use flate2::read::GzDecoder;
+use std::collections::HashMap;
+use std::io::{self, Read};
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ImportError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("not a font format blocklet can import")]
+    UnknownFormat,
+    #[error("invalid PSF font: {0}")]
+    Psf(String),
+    #[error("invalid BDF font on line {line}: {reason}")]
+    Bdf { line: usize, reason: String },
+    #[error("the font is larger than {} MiB once decompressed", MAX_DECOMPRESSED >> 20)]
+    TooLarge,
+    #[cfg(feature = "ttf")]
+    #[error("invalid outline font: {0}")]
+    Ttf(String),
+}
+
+/// The most a compressed font may expand to. Console fonts are a few
+/// kilobytes, so anything larger is not one.
+const MAX_DECOMPRESSED: u64 = 16 << 20;
+
+/// How many bitmap pixels one character cell covers
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Resolution {
+    /// One pixel per cell, drawn with full blocks
+    Full,
+    /// One pixel wide and two tall, drawn with half blocks. Terminal cells
+    /// are about twice as tall as they are wide, so pixels stay square.
+    #[default]
+    Half,
+    /// Two pixels wide and two tall, drawn with quadrant blocks
+    Quadrant,
+}
+
+impl Resolution {
+    /// Names accepted on the command line
+    pub const NAMES: [&'static str; 3] = ["full", "half", "quadrant"];
+
+    /// Pixels covered by one cell, across and down
+    fn cell_pixels(self) -> (usize, usize) {
+        match self {
+            Resolution::Full => (1, 1),
+            Resolution::Half => (1, 2),
+            Resolution::Quadrant => (2, 2),
+        }
+    }
+}
+
+impl FromStr for Resolution {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "full" => Ok(Resolution::Full),
+            "half" => Ok(Resolution::Half),
+            "quadrant" => Ok(Resolution::Quadrant),
+            _ => Err(format!("Unknown resolution '{}'", s)),
+        }
+    }
+}
+
+/// How bitmap glyphs become block glyphs
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ImportOptions {
+    pub resolution: Resolution,
+    /// Draw a drop shadow below and to the right of every glyph
+    pub shadow: bool,
+}
+
+/// A one-bit glyph image
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bitmap {
+    pub width: usize,
+    pub height: usize,
+    /// Rows of pixels from the top, `true` where the glyph is drawn
+    pub pixels: Vec<bool>,
+}
+
+impl Bitmap {
+    /// Whether the pixel at (`x`, `y`) is set, treating pixels outside the
+    /// bitmap as unset
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.pixels[y * self.width + x]
+    }
+
+    /// Convert the bitmap into rows of block characters
+    pub fn to_rows(&self, resolution: Resolution) -> Vec<String> {
+        let (across, down) = resolution.cell_pixels();
+        let columns = self.width.div_ceil(across);
+        (0..self.height.div_ceil(down))
+            .map(|row| {
+                (0..columns)
+                    .map(|column| {
+                        let (x, y) = (column * across, row * down);
+                        match resolution {
+                            Resolution::Full => {
+                                if self.get(x, y) {
+                                    '█'
+                                } else {
+                                    ' '
+                                }
+                            }
+                            Resolution::Half => match (self.get(x, y), self.get(x, y + 1)) {
+                                (true, true) => '█',
+                                (true, false) => '▀',
+                                (false, true) => '▄',
+                                (false, false) => ' ',
+                            },
+                            Resolution::Quadrant => transform::quadrant_char([
+                                self.get(x, y),
+                                self.get(x + 1, y),
+                                self.get(x, y + 1),
+                                self.get(x + 1, y + 1),
+                            ]),
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Build a block font from bitmap glyphs, all as tall as `height` pixels.
+///
+/// Control characters are left out. A font with no space gets a blank one,
+/// and one with no `?` gets a hollow box, so characters that fall back to it
+/// still show up.
+pub fn bitmap_font(
+    name: &str,
+    description: &str,
+    height: usize,
+    glyphs: impl IntoIterator<Item = (char, Bitmap)>,
+    options: &ImportOptions,
+    source: FontSource,
+) -> Font {
+    let (across, down) = options.resolution.cell_pixels();
+    let rows = height.div_ceil(down) + options.shadow as usize;
+    let convert = |bitmap: &Bitmap| {
+        let mut data = bitmap.to_rows(options.resolution);
+        // Taken from the bitmap rather than the rows, which a glyph no pixels tall does not have
+        let columns = bitmap.width.div_ceil(across);
+        data.resize(height.div_ceil(down), " ".repeat(columns));
+        if options.shadow {
+            // A free column and row for the shadow to fall into
+            for row in &mut data {
+                row.push(' ');
+            }
+            data.push(" ".repeat(columns + 1));
+            data = transform::shadow(&data);
+        }
+        let width = data.first().map_or(columns, |row| row.chars().count()) as u32;
+        FontCharacter {
+            width,
+            height: rows as u32,
+            data,
+        }
+    };
+
+    let mut characters: HashMap<char, FontCharacter> = HashMap::new();
+    let mut widest = 0;
+    let mut widest_bitmap = 0;
+    for (ch, bitmap) in glyphs {
+        if ch.is_control() {
+            continue;
+        }
+        let glyph = convert(&bitmap);
+        widest = widest.max(glyph.width);
+        widest_bitmap = widest_bitmap.max(bitmap.width);
+        characters.insert(ch, glyph);
+    }
+
+    characters.entry(' ').or_insert_with(|| FontCharacter {
+        width: widest.max(1),
+        height: rows as u32,
+        data: vec![" ".repeat(widest.max(1) as usize); rows],
+    });
+    characters
+        .entry(FALLBACK_CHARACTER)
+        .or_insert_with(|| convert(&placeholder(widest_bitmap.max(1), height)));
+
+    Font {
+        name: name.to_string(),
+        description: description.to_string(),
+        height: rows as u32,
+        characters,
+        source,
+    }
+}
+
+/// The outline of a box filling a `width` x `height` glyph
+fn placeholder(width: usize, height: usize) -> Bitmap {
+    let pixels = (0..height)
+        .flat_map(|y| {
+            (0..width).map(move |x| x == 0 || y == 0 || x + 1 == width || y + 1 == height)
+        })
+        .collect();
+    Bitmap {
+        width,
+        height,
+        pixels,
+    }
+}
+
+/// Import a font from the contents of a file, which may be gzip-compressed
+/// like most console fonts. The format is recognised from the data itself.
+pub fn import(
+    data: &[u8],
+    name: &str,
+    options: &ImportOptions,
+    source: FontSource,
+) -> Result<Font, ImportError> {
+    if data.starts_with(&[0x1f, 0x8b]) {
+        let mut decompressed = Vec::new();
+        GzDecoder::new(data)
+            .take(MAX_DECOMPRESSED + 1)
+            .read_to_end(&mut decompressed)?;
+        if decompressed.len() as u64 > MAX_DECOMPRESSED {
+            return Err(ImportError::TooLarge);
+        }
+        return import(&decompressed, name, options, source);
+    }
+    if psf::is_psf(data) {
+        let font = psf::parse(data)?;
+        let description = format!(
+            "Converted from the {}x{} PSF font {}",
+            font.width, font.height, name
+        );
+        return Ok(bitmap_font(
+            name,
+            &description,
+            font.height,
+            font.glyphs(),
+            options,
+            source,
+        ));
+    }
+    if bdf::is_bdf(data) {
+        let font = bdf::parse(data)?;
+        let description = format!("Converted from the BDF font {}", font.name);
+        return Ok(bitmap_font(
+            name,
+            &description,
+            font.height(),
+            font.glyphs,
+            options,
+            source,
+        ));
+    }
+    Err(ImportError::UnknownFormat)
+}
+
+/// A font name from a file name, without extensions such as `.psf.gz`
+pub fn font_name(path: &std::path::Path) -> String {
+    let file_name = path
+        .file_name()
+        .map_or_else(String::new, |name| name.to_string_lossy().into_owned());
+    let stem = file_name.split('.').next().unwrap_or_default();
+    if stem.is_empty() {
+        "font".to_string()
+    } else {
+        stem.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bitmap(rows: &[&str]) -> Bitmap {
+        Bitmap {
+            width: rows[0].len(),
+            height: rows.len(),
+            pixels: rows
+                .iter()
+                .flat_map(|row| row.chars().map(|c| c == '#'))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_resolutions() {
+        let glyph = bitmap(&[".##.", "#..#", "####", "#..#", "#..#"]);
+        assert_eq!(
+            glyph.to_rows(Resolution::Full),
+            [" ██ ", "█  █", "████", "█  █", "█  █"]
+        );
+        assert_eq!(glyph.to_rows(Resolution::Half), ["▄▀▀▄", "█▀▀█", "▀  ▀"]);
+        assert_eq!(glyph.to_rows(Resolution::Quadrant), ["▞▚", "▛▜", "▘▝"]);
+    }
+
+    #[test]
+    fn test_bitmap_font() {
+        let glyphs = [
+            ('I', bitmap(&["#.", "#.", "#.", ".."])),
+            ('\u{7}', bitmap(&["##", "##", "##", "##"])),
+        ];
+        let options = ImportOptions {
+            resolution: Resolution::Half,
+            shadow: true,
+        };
+        let font = bitmap_font("test", "", 4, glyphs, &options, FontSource::BuiltIn);
+        assert_eq!(font.height, 3);
+        assert_eq!(font.characters[&'I'].data, ["█╗ ", "▀║ ", "╚╝ "]);
+        assert_eq!(font.characters[&'I'].width, 3);
+        assert!(!font.characters.contains_key(&'\u{7}'));
+        assert_eq!(font.characters[&' '].data, ["   "; 3]);
+        // The missing `?` is a box as wide as the widest glyph
+        assert_eq!(font.characters[&'?'].data, ["██╗", "██║", "╚═╝"]);
+    }
+
+    #[test]
+    fn test_placeholder_glyph() {
+        let glyphs = [('I', bitmap(&["#...", "#...", "#...", "#..."]))];
+        let options = ImportOptions {
+            resolution: Resolution::Full,
+            shadow: false,
+        };
+        let font = bitmap_font("test", "", 4, glyphs, &options, FontSource::BuiltIn);
+        assert_eq!(font.characters[&'?'].data, ["████", "█  █", "█  █", "████"]);
+    }
+
+    #[test]
+    fn test_gzip_limit() {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+        use std::io::Write;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+        encoder
+            .write_all(&vec![0; MAX_DECOMPRESSED as usize + 1])
+            .unwrap();
+        let data = encoder.finish().unwrap();
+        assert!(matches!(
+            import(
+                &data,
+                "bomb",
+                &ImportOptions::default(),
+                FontSource::BuiltIn
+            ),
+            Err(ImportError::TooLarge)
+        ));
+    }
+
+    #[test]
+    fn test_bitmap_font_without_rows() {
+        let glyphs = [(
+            'A',
+            Bitmap {
+                width: 3,
+                height: 0,
+                pixels: Vec::new(),
+            },
+        )];
+        let options = ImportOptions {
+            resolution: Resolution::Full,
+            shadow: true,
+        };
+        let font = bitmap_font("flat", "", 0, glyphs, &options, FontSource::BuiltIn);
+        assert_eq!(font.height, 1);
+        assert_eq!(font.characters[&'A'].data, ["    "]);
+        assert_eq!(font.characters[&'A'].width, 4);
+    }
+
+    #[test]
+    fn test_font_name() {
+        assert_eq!(
+            font_name(std::path::Path::new(
+                "/usr/share/kbd/consolefonts/Lat2-Terminus16.psf.gz"
+            )),
+            "Lat2-Terminus16"
+        );
+    }
+}
@@ -0,0 +1,258 @@
+//! PC Screen Font (PSF) console fonts, versions 1 and 2, as shipped in
+//! `/usr/share/kbd/consolefonts` or `/usr/share/consolefonts`.
+
+use super::{Bitmap, ImportError};
+use std::collections::HashSet;
+
+const PSF1_MAGIC: [u8; 2] = [0x36, 0x04];
+const PSF1_MODE512: u8 = 0x01;
+const PSF1_MODEHASTAB: u8 = 0x02;
+const PSF1_MODESEQ: u8 = 0x04;
+const PSF1_SEPARATOR: u16 = 0xFFFF;
+const PSF1_STARTSEQ: u16 = 0xFFFE;
+
+const PSF2_MAGIC: [u8; 4] = [0x72, 0xb5, 0x4a, 0x86];
+const PSF2_HAS_UNICODE_TABLE: u32 = 0x01;
+const PSF2_SEPARATOR: u8 = 0xFF;
+const PSF2_STARTSEQ: u8 = 0xFE;
+
+/// The glyphs of a PSF font
+#[derive(Clone, Debug)]
+pub struct PsfFont {
+    pub width: usize,
+    pub height: usize,
+    pub bitmaps: Vec<Bitmap>,
+    /// The characters each glyph draws, from the font's Unicode table
+    pub unicode: Option<Vec<Vec<char>>>,
+}
+
+impl PsfFont {
+    /// Every glyph with a character it draws. Without a Unicode table glyph
+    /// `n` is taken to draw U+00nn, the Latin-1 layout of most such fonts.
+    /// A character drawn by several glyphs keeps the first.
+    pub fn glyphs(&self) -> Vec<(char, Bitmap)> {
+        let mut seen = HashSet::new();
+        let mut glyphs = Vec::new();
+        for (index, bitmap) in self.bitmaps.iter().enumerate() {
+            let chars = match &self.unicode {
+                Some(table) => table.get(index).cloned().unwrap_or_default(),
+                None => char::from_u32(index as u32).into_iter().collect(),
+            };
+            for ch in chars {
+                if seen.insert(ch) {
+                    glyphs.push((ch, bitmap.clone()));
+                }
+            }
+        }
+        glyphs
+    }
+}
+
+/// Whether `data` starts like a PSF1 or PSF2 font
+pub fn is_psf(data: &[u8]) -> bool {
+    data.starts_with(&PSF1_MAGIC) || data.starts_with(&PSF2_MAGIC)
+}
+
+pub fn parse(data: &[u8]) -> Result<PsfFont, ImportError> {
+    if data.starts_with(&PSF2_MAGIC) {
+        parse_psf2(data)
+    } else if data.starts_with(&PSF1_MAGIC) {
+        parse_psf1(data)
+    } else {
+        Err(ImportError::UnknownFormat)
+    }
+}
+
+fn parse_psf1(data: &[u8]) -> Result<PsfFont, ImportError> {
+    let (mode, height) = match data.get(2..4) {
+        Some(&[mode, height]) => (mode, height as usize),
+        _ => return Err(invalid("truncated header")),
+    };
+    let count = if mode & PSF1_MODE512 != 0 { 512 } else { 256 };
+    let (bitmaps, rest) = read_bitmaps(&data[4..], count, 8, height)?;
+
+    let unicode = if mode & (PSF1_MODEHASTAB | PSF1_MODESEQ) != 0 {
+        let values: Vec<u16> = rest
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+        let mut entries = values.split(|&value| value == PSF1_SEPARATOR);
+        let table = (0..count)
+            .map(|_| {
+                let entry = entries
+                    .next()
+                    .ok_or_else(|| invalid("truncated Unicode table"))?;
+                // Sequences of combining characters follow the single characters
+                let singles = entry
+                    .split(|&value| value == PSF1_STARTSEQ)
+                    .next()
+                    .unwrap_or_default();
+                Ok(singles
+                    .iter()
+                    .filter_map(|&value| char::from_u32(value as u32))
+                    .collect())
+            })
+            .collect::<Result<Vec<Vec<char>>, ImportError>>()?;
+        Some(table)
+    } else {
+        None
+    };
+
+    Ok(PsfFont {
+        width: 8,
+        height,
+        bitmaps,
+        unicode,
+    })
+}
+
+fn parse_psf2(data: &[u8]) -> Result<PsfFont, ImportError> {
+    let field = |index: usize| -> Result<u32, ImportError> {
+        let bytes = data
+            .get(4 + index * 4..8 + index * 4)
+            .ok_or_else(|| invalid("truncated header"))?;
+        Ok(u32::from_le_bytes(bytes.try_into().expect("four bytes")))
+    };
+    let (header_size, flags, count) = (field(1)? as usize, field(2)?, field(3)? as usize);
+    let (glyph_size, height, width) = (field(4)? as usize, field(5)? as usize, field(6)? as usize);
+    if glyph_size != height * width.div_ceil(8) {
+        return Err(invalid("glyph size does not match its width and height"));
+    }
+    let body = data
+        .get(header_size..)
+        .ok_or_else(|| invalid("truncated header"))?;
+    let (bitmaps, rest) = read_bitmaps(body, count, width, height)?;
+
+    let unicode = if flags & PSF2_HAS_UNICODE_TABLE != 0 {
+        let mut entries = rest.split(|&byte| byte == PSF2_SEPARATOR);
+        let table = (0..count)
+            .map(|_| {
+                let entry = entries
+                    .next()
+                    .ok_or_else(|| invalid("truncated Unicode table"))?;
+                // Sequences of combining characters follow the single characters
+                let singles = entry
+                    .split(|&byte| byte == PSF2_STARTSEQ)
+                    .next()
+                    .unwrap_or_default();
+                let singles = std::str::from_utf8(singles)
+                    .map_err(|_| invalid("Unicode table is not UTF-8"))?;
+                Ok(singles.chars().collect())
+            })
+            .collect::<Result<Vec<Vec<char>>, ImportError>>()?;
+        Some(table)
+    } else {
+        None
+    };
+
+    Ok(PsfFont {
+        width,
+        height,
+        bitmaps,
+        unicode,
+    })
+}
+
+/// Read `count` glyph bitmaps with rows padded to whole bytes, most
+/// significant bit first, returning them and the data after them
+fn read_bitmaps(
+    data: &[u8],
+    count: usize,
+    width: usize,
+    height: usize,
+) -> Result<(Vec<Bitmap>, &[u8]), ImportError> {
+    let row_bytes = width.div_ceil(8);
+    let glyph_size = row_bytes * height;
+    let size = count
+        .checked_mul(glyph_size)
+        .filter(|&size| size <= data.len())
+        .ok_or_else(|| invalid("truncated glyph data"))?;
+    let bitmaps = data[..size]
+        .chunks_exact(glyph_size.max(1))
+        .take(count)
+        .map(|glyph| Bitmap {
+            width,
+            height,
+            pixels: glyph
+                .chunks_exact(row_bytes)
+                .flat_map(|row| (0..width).map(move |x| row[x / 8] & (0x80 >> (x % 8)) != 0))
+                .collect(),
+        })
+        .collect();
+    Ok((bitmaps, &data[size..]))
+}
+
+fn invalid(reason: &str) -> ImportError {
+    ImportError::Psf(reason.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 4x4 PSF2 font with an `A` and a `?`, the `A` also drawing `Α`
+    fn psf2() -> Vec<u8> {
+        let mut data = PSF2_MAGIC.to_vec();
+        for field in [0, 32, PSF2_HAS_UNICODE_TABLE, 2, 4, 4, 4] {
+            data.extend_from_slice(&u32::to_le_bytes(field));
+        }
+        data.extend_from_slice(&[0b0110_0000, 0b1001_0000, 0b1111_0000, 0b1001_0000]);
+        data.extend_from_slice(&[0b1110_0000, 0b0010_0000, 0b0100_0000, 0b0100_0000]);
+        data.extend_from_slice("AΑ".as_bytes());
+        data.extend_from_slice(&[PSF2_STARTSEQ, b'A', 0xCC, 0x81, PSF2_SEPARATOR]);
+        data.extend_from_slice(&[b'?', PSF2_SEPARATOR]);
+        data
+    }
+
+    #[test]
+    fn test_psf2() {
+        let font = parse(&psf2()).unwrap();
+        assert_eq!((font.width, font.height), (4, 4));
+        let glyphs = font.glyphs();
+        let chars: Vec<char> = glyphs.iter().map(|(ch, _)| *ch).collect();
+        assert_eq!(chars, ['A', 'Α', '?']);
+        assert_eq!(
+            glyphs[0].1.to_rows(crate::import::Resolution::Full),
+            [" ██ ", "█  █", "████", "█  █"]
+        );
+    }
+
+    #[test]
+    fn test_psf1_without_table() {
+        let mut data = vec![PSF1_MAGIC[0], PSF1_MAGIC[1], 0, 2];
+        data.resize(4 + 256 * 2, 0);
+        data[4 + b'X' as usize * 2] = 0b1000_0001;
+        let font = parse(&data).unwrap();
+        assert_eq!(font.bitmaps.len(), 256);
+        let (ch, bitmap) = &font.glyphs()[b'X' as usize];
+        assert_eq!(*ch, 'X');
+        assert!(bitmap.get(0, 0) && bitmap.get(7, 0) && !bitmap.get(1, 0) && !bitmap.get(0, 1));
+    }
+
+    #[test]
+    fn test_import_gzipped() {
+        use crate::font::FontSource;
+        use crate::import::{import, ImportOptions};
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&psf2()).unwrap();
+        let font = import(
+            &encoder.finish().unwrap(),
+            "tiny",
+            &ImportOptions::default(),
+            FontSource::BuiltIn,
+        )
+        .unwrap();
+        assert_eq!(font.height, 2);
+        assert_eq!(font.characters[&'A'].data, ["▄▀▀▄", "█▀▀█"]);
+        assert_eq!(font.characters[&' '].width, 4);
+    }
+
+    #[test]
+    fn test_truncated() {
+        let data = psf2();
+        assert!(matches!(parse(&data[..40]), Err(ImportError::Psf(_))));
+    }
+}
@@ -0,0 +1,196 @@
+//! TrueType and OpenType outline fonts, rasterised at a chosen pixel height.
+//!
+//! Glyphs are drawn with the baseline at the font's ascent, and a pixel is
+//! set where the outline covers at least half of it.
+
+use super::{bitmap_font, Bitmap, ImportError, ImportOptions};
+use crate::font::{Font, FontSource};
+use ab_glyph::{point, Font as _, FontRef, GlyphId, ScaleFont};
+
+/// The smallest coverage that sets a pixel
+const THRESHOLD: f32 = 0.5;
+
+/// Whether `data` starts like a TrueType, OpenType or TrueType collection font
+pub fn is_ttf(data: &[u8]) -> bool {
+    [b"\x00\x01\x00\x00", b"OTTO", b"true", b"ttcf"]
+        .iter()
+        .any(|magic| data.starts_with(*magic))
+}
+
+/// Rasterise the glyphs the font has for `chars` into cells `size` pixels
+/// tall. Collections use their first font.
+///
+/// Outline fonts often cover thousands of characters, so only the ones that
+/// will be drawn are rasterised.
+pub fn rasterise(
+    data: &[u8],
+    size: usize,
+    chars: impl IntoIterator<Item = char>,
+) -> Result<Vec<(char, Bitmap)>, ImportError> {
+    if size == 0 {
+        return Err(invalid("the size must be at least one pixel"));
+    }
+    let font =
+        FontRef::try_from_slice(data).map_err(|_| invalid("not a TrueType or OpenType font"))?;
+    let scaled = font.as_scaled(size as f32);
+    let mut chars: Vec<char> = chars.into_iter().collect();
+    chars.sort_unstable();
+    chars.dedup();
+    Ok(chars
+        .into_iter()
+        // Glyph 0 is the font's own missing glyph, left to the `?` fallback
+        .filter_map(|ch| {
+            Some(font.glyph_id(ch))
+                .filter(|id| id.0 != 0)
+                .map(|id| (ch, draw(&scaled, id, size)))
+        })
+        .collect())
+}
+
+/// The characters to rasterise for rendering `text`: its own characters,
+/// their uppercase forms, which the renderer falls back to, and printable
+/// ASCII for the hyphens and brackets that wrapping and bidi reordering add
+pub fn characters_for(text: &str) -> impl Iterator<Item = char> + '_ {
+    text.chars()
+        .flat_map(|ch| std::iter::once(ch).chain(ch.to_uppercase()))
+        .chain(' '..='~')
+}
+
+/// Draw a glyph into a cell as wide as it advances and `height` pixels tall.
+/// Glyphs that reach left of their origin, like the tail of a `j`, are moved
+/// right so none of them is clipped; pixels above or below the cell are.
+fn draw<F: ab_glyph::Font>(font: &impl ScaleFont<F>, id: GlyphId, height: usize) -> Bitmap {
+    let advance = font.h_advance(id).round().max(0.0) as usize;
+    let glyph = id.with_scale_and_position(font.scale(), point(0.0, font.ascent().round()));
+    let Some(outline) = font.font().outline_glyph(glyph) else {
+        return Bitmap {
+            width: advance,
+            height,
+            pixels: vec![false; advance * height],
+        };
+    };
+
+    // Pixel bounds are whole numbers, so the shift is too
+    let bounds = outline.px_bounds();
+    let shift = (-bounds.min.x).max(0.0) as usize;
+    // Glyphs that overhang to the right, like many italics, widen the cell
+    let width = (advance + shift).max((bounds.max.x.max(0.0)) as usize + shift);
+    let mut pixels = vec![false; width * height];
+    outline.draw(|x, y, coverage| {
+        let x = bounds.min.x as i32 + shift as i32 + x as i32;
+        let y = bounds.min.y as i32 + y as i32;
+        if coverage >= THRESHOLD
+            && (0..width as i32).contains(&x)
+            && (0..height as i32).contains(&y)
+        {
+            pixels[y as usize * width + x as usize] = true;
+        }
+    });
+    Bitmap {
+        width,
+        height,
+        pixels,
+    }
+}
+
+/// Import the glyphs for `chars` from an outline font rasterised `size`
+/// pixels tall
+pub fn import(
+    data: &[u8],
+    name: &str,
+    size: usize,
+    chars: impl IntoIterator<Item = char>,
+    options: &ImportOptions,
+    source: FontSource,
+) -> Result<Font, ImportError> {
+    let glyphs = rasterise(data, size, chars)?;
+    let description = format!(
+        "Rasterised from the outline font {} at {} pixels",
+        name, size
+    );
+    Ok(bitmap_font(
+        name,
+        &description,
+        size,
+        glyphs,
+        options,
+        source,
+    ))
+}
+
+fn invalid(reason: &str) -> ImportError {
+    ImportError::Ttf(reason.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::import::Resolution;
+
+    /// A font 1000 units tall with an ascent of 800, whose `I` is a bar from
+    /// x 200 to 400 and whose `j` is a bar from x -200 to 200 that reaches
+    /// below the baseline
+    const FONT: &[u8] = include_bytes!("../../tests/fonts/blocks.ttf");
+
+    #[test]
+    fn test_rasterise() {
+        let glyphs = rasterise(FONT, 10, "Ij?".chars()).unwrap();
+        let chars: Vec<char> = glyphs.iter().map(|(ch, _)| *ch).collect();
+        // The font has no `?`
+        assert_eq!(chars, ['I', 'j']);
+
+        let i = &glyphs[0].1;
+        assert_eq!((i.width, i.height), (6, 10));
+        assert_eq!(i.to_rows(Resolution::Full)[0], "  ██  ");
+        assert_eq!(i.to_rows(Resolution::Full)[8], "      ");
+
+        // Moved right by the two pixels it reaches left of its origin
+        let j = &glyphs[1].1;
+        assert_eq!((j.width, j.height), (6, 10));
+        assert_eq!(j.to_rows(Resolution::Full)[1], "      ");
+        assert_eq!(j.to_rows(Resolution::Full)[2], "████  ");
+        assert_eq!(j.to_rows(Resolution::Full)[9], "████  ");
+    }
+
+    #[test]
+    fn test_import() {
+        let options = ImportOptions {
+            resolution: Resolution::Half,
+            shadow: false,
+        };
+        let font = import(
+            FONT,
+            "blocks",
+            10,
+            characters_for("hi"),
+            &options,
+            FontSource::BuiltIn,
+        )
+        .unwrap();
+        assert_eq!(font.height, 5);
+        // Only the uppercase `I` exists, which the renderer falls back to
+        assert_eq!(
+            font.characters[&'I'].data,
+            ["  ██  "; 4]
+                .into_iter()
+                .chain(["      "])
+                .collect::<Vec<_>>()
+        );
+        assert!(font.characters.contains_key(&'?') && font.characters.contains_key(&' '));
+        assert!(!font.characters.contains_key(&'h'));
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(is_ttf(FONT));
+        assert!(!is_ttf(b"STARTFONT 2.1"));
+        assert!(matches!(
+            rasterise(b"\x00\x01\x00\x00", 16, ['A']),
+            Err(ImportError::Ttf(_))
+        ));
+        assert!(matches!(
+            rasterise(FONT, 0, ['I']),
+            Err(ImportError::Ttf(_))
+        ));
+    }
+}
@@ -0,0 +1,349 @@
+//! Inspection of fonts for font designers: the full character map, glyphs
+//! whose dimensions disagree with what they declare, and Unicode coverage.
+
+use crate::font::Font;
+use std::fmt;
+use std::ops::Range;
+use unicode_blocks::find_unicode_block;
+
+/// A problem with the dimensions of one glyph
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GlyphIssue {
+    /// A run of rows is not as wide as the glyph's declared width
+    RowWidth {
+        rows: Range<usize>,
+        width: usize,
+        declared: u32,
+    },
+    /// The number of rows differs from the glyph's declared height
+    RowCount { rows: usize, declared: u32 },
+    /// The glyph's declared height differs from the font's
+    Height { declared: u32, font: u32 },
+}
+
+impl fmt::Display for GlyphIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GlyphIssue::RowWidth {
+                rows,
+                width,
+                declared,
+            } if rows.len() == 1 => {
+                write!(
+                    f,
+                    "row {} is {} columns wide but the glyph declares {}",
+                    rows.start + 1,
+                    width,
+                    declared
+                )
+            }
+            GlyphIssue::RowWidth {
+                rows,
+                width,
+                declared,
+            } => {
+                write!(
+                    f,
+                    "rows {}-{} are {} columns wide but the glyph declares {}",
+                    rows.start + 1,
+                    rows.end,
+                    width,
+                    declared
+                )
+            }
+            GlyphIssue::RowCount { rows, declared } => {
+                write!(f, "has {} rows but declares a height of {}", rows, declared)
+            }
+            GlyphIssue::Height { declared, font } => {
+                write!(
+                    f,
+                    "declares a height of {} but the font is {} rows tall",
+                    declared, font
+                )
+            }
+        }
+    }
+}
+
+/// Every dimension problem in a font, ordered by character
+pub fn glyph_issues(font: &Font) -> Vec<(char, GlyphIssue)> {
+    let mut chars: Vec<char> = font.characters.keys().copied().collect();
+    chars.sort_unstable();
+
+    let mut issues = Vec::new();
+    for ch in chars {
+        let glyph = &font.characters[&ch];
+        if glyph.height != font.height {
+            issues.push((
+                ch,
+                GlyphIssue::Height {
+                    declared: glyph.height,
+                    font: font.height,
+                },
+            ));
+        }
+        if glyph.data.len() != glyph.height as usize {
+            issues.push((
+                ch,
+                GlyphIssue::RowCount {
+                    rows: glyph.data.len(),
+                    declared: glyph.height,
+                },
+            ));
+        }
+        let widths: Vec<usize> = glyph.data.iter().map(|row| row.chars().count()).collect();
+        let mut start = 0;
+        while start < widths.len() {
+            // Report each run of rows with the same width once
+            let width = widths[start];
+            let end = start + widths[start..].iter().take_while(|&&w| w == width).count();
+            if width != glyph.width as usize {
+                issues.push((
+                    ch,
+                    GlyphIssue::RowWidth {
+                        rows: start..end,
+                        width,
+                        declared: glyph.width,
+                    },
+                ));
+            }
+            start = end;
+        }
+    }
+    issues
+}
+
+/// How much of one Unicode block a font covers
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockCoverage {
+    pub name: &'static str,
+    pub start: u32,
+    pub end: u32,
+    /// Characters in the block the font can draw
+    pub covered: usize,
+    /// Characters in the block, not counting control characters
+    pub total: usize,
+}
+
+impl BlockCoverage {
+    pub fn is_complete(&self) -> bool {
+        self.covered == self.total
+    }
+}
+
+/// Coverage of every Unicode block the font has at least one glyph in.
+///
+/// Lowercase letters count as covered when the font has the uppercase glyph,
+/// since the renderer draws them that way.
+pub fn block_coverage(font: &Font) -> Vec<BlockCoverage> {
+    let mut blocks: Vec<BlockCoverage> = Vec::new();
+    for &ch in font.characters.keys() {
+        let Some(block) = find_unicode_block(ch) else {
+            continue;
+        };
+        if blocks.iter().any(|b| b.start == block.start()) {
+            continue;
+        }
+        let chars = (block.start()..=block.end())
+            .filter_map(char::from_u32)
+            .filter(|c| !c.is_control());
+        let (mut covered, mut total) = (0, 0);
+        for c in chars {
+            total += 1;
+            covered += font.has_character(c) as usize;
+        }
+        blocks.push(BlockCoverage {
+            name: block.name(),
+            start: block.start(),
+            end: block.end(),
+            covered,
+            total,
+        });
+    }
+    blocks.sort_by_key(|block| block.start);
+    blocks
+}
+
+/// Draw every glyph of a font in a grid no wider than `max_width` columns,
+/// each under a label with its code point. Glyphs with dimension problems
+/// are marked with `!`.
+pub fn character_map(font: &Font, max_width: usize) -> String {
+    let mut chars: Vec<char> = font.characters.keys().copied().collect();
+    chars.sort_unstable();
+    let flagged: Vec<char> = glyph_issues(font).into_iter().map(|(ch, _)| ch).collect();
+
+    let label = |ch: char| {
+        let mut label = format!("U+{:04X}", ch as u32);
+        if !ch.is_whitespace() && !ch.is_control() {
+            label.push(' ');
+            label.push(ch);
+        }
+        if flagged.contains(&ch) {
+            label.push_str(" !");
+        }
+        label
+    };
+    let glyph_width = |ch: char| {
+        let glyph = &font.characters[&ch];
+        glyph
+            .data
+            .iter()
+            .map(|row| row.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(glyph.width as usize)
+    };
+    let cell_width = chars
+        .iter()
+        .map(|&ch| glyph_width(ch).max(label(ch).chars().count()))
+        .max()
+        .unwrap_or(0)
+        + 2;
+    let columns = (max_width / cell_width.max(1)).max(1);
+    let rows = font
+        .characters
+        .values()
+        .map(|glyph| glyph.data.len())
+        .max()
+        .unwrap_or(0);
+
+    let mut out: Vec<String> = Vec::new();
+    for group in chars.chunks(columns) {
+        if !out.is_empty() {
+            out.push(String::new());
+        }
+        let mut lines = vec![String::new(); rows + 1];
+        for &ch in group {
+            pad_push(&mut lines[0], &label(ch), cell_width);
+            for (row, line) in lines[1..].iter_mut().enumerate() {
+                let data = font.characters[&ch]
+                    .data
+                    .get(row)
+                    .map_or("", String::as_str);
+                pad_push(line, data, cell_width);
+            }
+        }
+        out.extend(lines.into_iter().map(|line| line.trim_end().to_string()));
+    }
+    out.join("\n")
+}
+
+/// Append `text` padded with spaces to `width` columns
+fn pad_push(line: &mut String, text: &str, width: usize) {
+    line.push_str(text);
+    line.extend(std::iter::repeat_n(
+        ' ',
+        width.saturating_sub(text.chars().count()),
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font::{get_font, FontCharacter, FontSource};
+    use std::collections::HashMap;
+
+    fn font(glyphs: &[(char, u32, &[&str])]) -> Font {
+        let characters: HashMap<char, FontCharacter> = glyphs
+            .iter()
+            .map(|&(ch, width, rows)| {
+                let data = rows.iter().map(|row| row.to_string()).collect();
+                (
+                    ch,
+                    FontCharacter {
+                        width,
+                        height: 2,
+                        data,
+                    },
+                )
+            })
+            .collect();
+        Font {
+            name: "test".to_string(),
+            description: String::new(),
+            height: 2,
+            characters,
+            source: FontSource::BuiltIn,
+        }
+    }
+
+    #[test]
+    fn test_glyph_issues() {
+        let font = font(&[
+            ('A', 2, &["██", "██"]),
+            ('B', 2, &["██", "█"]),
+            ('C', 2, &["█"]),
+            ('D', 2, &["█", "█"]),
+        ]);
+        let issues = glyph_issues(&font);
+        assert_eq!(
+            issues,
+            [
+                (
+                    'B',
+                    GlyphIssue::RowWidth {
+                        rows: 1..2,
+                        width: 1,
+                        declared: 2
+                    }
+                ),
+                (
+                    'C',
+                    GlyphIssue::RowCount {
+                        rows: 1,
+                        declared: 2
+                    }
+                ),
+                (
+                    'C',
+                    GlyphIssue::RowWidth {
+                        rows: 0..1,
+                        width: 1,
+                        declared: 2
+                    }
+                ),
+                (
+                    'D',
+                    GlyphIssue::RowWidth {
+                        rows: 0..2,
+                        width: 1,
+                        declared: 2
+                    }
+                ),
+            ]
+        );
+        assert_eq!(
+            issues[0].1.to_string(),
+            "row 2 is 1 columns wide but the glyph declares 2"
+        );
+        assert_eq!(
+            issues[3].1.to_string(),
+            "rows 1-2 are 1 columns wide but the glyph declares 2"
+        );
+    }
+
+    #[test]
+    fn test_block_coverage() {
+        let blocks = block_coverage(get_font("standard_shadow").unwrap());
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].name, "Basic Latin");
+        assert_eq!(blocks[0].total, 95);
+        // Lowercase letters are drawn with the uppercase glyphs
+        assert_eq!(blocks[0].covered, 41 + 26);
+        assert!(!blocks[0].is_complete());
+    }
+
+    #[test]
+    fn test_character_map() {
+        let font = font(&[
+            ('A', 2, &["██", "██"]),
+            ('B', 2, &["██", "█"]),
+            (' ', 2, &["  ", "  "]),
+        ]);
+        assert_eq!(
+            character_map(&font, 40),
+            "U+0020      U+0041 A    U+0042 B !\n            ██          ██\n            ██          █"
+        );
+        assert_eq!(character_map(&font, 10).lines().count(), 3 * 3 + 2);
+    }
+}
@@ -0,0 +1,19 @@
+//! Blocklet renders text as ASCII art built from Unicode block characters.
+//!
+//! The [`renderer`] module turns text into rows of glyphs from a [`font::Font`],
+//! either as a `String` or streamed straight into any `std::io::Write`, and the
+//! [`output`] backends convert that art into other formats such as HTML.
+
+pub mod ascii;
+mod boxdraw;
+pub mod color;
+pub mod decorate;
+#[cfg(feature = "editor")]
+pub mod editor;
+pub mod font;
+pub mod fontfile;
+pub mod import;
+pub mod inspect;
+pub mod output;
+pub mod renderer;
+pub mod transform;
@@ -1,9 +1,26 @@
-use clap::{Arg, Command};
+use anyhow::{anyhow, Context, Result};
+use blocklet::ascii::AsciiOptions;
+use blocklet::color::Color;
+use blocklet::decorate::{self, BorderStyle, FrameOptions, Spacing};
+use blocklet::font::{font_names, fonts, get_font, Font, FontSource};
+use blocklet::fontfile::{self, LoadError};
+use blocklet::import::{self, ImportError, ImportOptions, Resolution};
+use blocklet::inspect;
+use blocklet::output::comment::{self, CommentOptions, CommentStyle};
+use blocklet::output::html::{self, HtmlOptions};
+use blocklet::output::literal::{self, LiteralOptions};
+use blocklet::output::{png, svg};
+use blocklet::output::{CellSize, ImageOptions, OutputFormat};
+use blocklet::renderer::{RenderOptions, Renderer};
+use blocklet::transform::{self, Rotation, ShadowDirection};
+use clap::parser::ValueSource;
+use clap::{Arg, ArgGroup, ArgMatches, Command};
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{self, BufWriter, IsTerminal, Write};
+use std::path::Path;
 use std::process;
 
-pub mod font;
-pub mod renderer;
-
 fn main() {
     let matches = Command::new("blocklet")
         .version("0.1.2")
@@ -24,14 +41,147 @@ fn main() {
                 .value_parser(clap::value_parser!(u32))
                 .default_value("0")
         )
+        .arg(
+            Arg::new("line-gap")
+                .long("line-gap")
+                .value_name("ROWS")
+                .help("Blank rows between lines of text, negative values overlap them")
+                .value_parser(clap::value_parser!(i32))
+                .allow_negative_numbers(true)
+                .default_value("1")
+        )
+        .arg(
+            Arg::new("preserve-whitespace")
+                .short('p')
+                .long("preserve-whitespace")
+                .help("Keep runs of spaces, expand tabs and break lines at newlines in the text")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("tab-width")
+                .long("tab-width")
+                .value_name("COLUMNS")
+                .help("Distance between tab stops with --preserve-whitespace")
+                .value_parser(clap::value_parser!(u32).range(1..))
+                .default_value("4")
+        )
+        .arg(
+            Arg::new("shadow-direction")
+                .long("shadow-direction")
+                .value_name("SIDE")
+                .help("Side the drop shadow falls on, left suits right-to-left text")
+                .value_parser(clap::builder::PossibleValuesParser::new(ShadowDirection::NAMES))
+                .default_value("right")
+        )
+        .arg(
+            Arg::new("direction")
+                .long("direction")
+                .value_name("DIRECTION")
+                .help("Lay glyphs out left to right or stack them top to bottom")
+                .value_parser(["horizontal", "vertical"])
+                .default_value("horizontal")
+        )
+        .arg(
+            Arg::new("rotate")
+                .long("rotate")
+                .value_name("DEGREES")
+                .help("Rotate the rendered art clockwise")
+                .value_parser(clap::builder::PossibleValuesParser::new(Rotation::NAMES))
+        )
+        .arg(
+            Arg::new("flip-h")
+                .long("flip-h")
+                .help("Mirror the rendered art left to right")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("flip-v")
+                .long("flip-v")
+                .help("Mirror the rendered art top to bottom")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("scale")
+                .long("scale")
+                .value_name("FACTOR")
+                .help("Enlarge the font by repeating every cell FACTOR times in both directions")
+                .value_parser(clap::value_parser!(u32).range(1..))
+        )
+        .arg(
+            Arg::new("scale-x")
+                .long("scale-x")
+                .value_name("FACTOR")
+                .help("Horizontal scale factor, overriding --scale")
+                .value_parser(clap::value_parser!(u32).range(1..))
+        )
+        .arg(
+            Arg::new("scale-y")
+                .long("scale-y")
+                .value_name("FACTOR")
+                .help("Vertical scale factor, overriding --scale")
+                .value_parser(clap::value_parser!(u32).range(1..))
+        )
         .arg(
             Arg::new("font")
                 .short('f')
                 .long("font")
                 .value_name("FONT")
-                .help("Font to use (standard, standard_shadow, standard_solid)")
+                .help("Font to use (see --list-fonts)")
+                .value_parser(clap::builder::PossibleValuesParser::new(font_names()))
                 .default_value("standard_shadow")
         )
+        .arg(
+            Arg::new("font-file")
+                .long("font-file")
+                .value_name("FILE")
+                .help("Load the font from a .blf font file or convert a PSF or BDF bitmap font")
+                .conflicts_with_all(["font", "no-shadow"])
+        )
+        .arg(
+            Arg::new("ttf")
+                .long("ttf")
+                .value_name("FILE")
+                .help("Rasterise a TrueType or OpenType font into block glyphs")
+                .conflicts_with_all(["font", "font-file", "no-shadow"])
+        )
+        .arg(
+            Arg::new("size")
+                .long("size")
+                .value_name("PIXELS")
+                .help("Pixel height to rasterise the --ttf font at, up to 512")
+                .value_parser(clap::value_parser!(u32).range(1..=512))
+                .default_value("16")
+                .requires("ttf")
+        )
+        .arg(
+            Arg::new("resolution")
+                .long("resolution")
+                .value_name("CELLS")
+                .help("Pixels per cell when converting a bitmap or outline font: one (full), one by two (half) or two by two (quadrant)")
+                .value_parser(clap::builder::PossibleValuesParser::new(Resolution::NAMES))
+                .default_value("half")
+        )
+        .arg(
+            Arg::new("add-shadow")
+                .long("add-shadow")
+                .help("Give a converted bitmap or outline font a drop shadow")
+                .requires("converted")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("list-fonts")
+                .long("list-fonts")
+                .help("List the available fonts with their heights, coverage and sources")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("showcase")
+                .long("showcase")
+                .value_name("TEXT")
+                .help("Render sample text in every available font")
+                .num_args(0..=1)
+                .default_missing_value("Blocklet")
+        )
         .arg(
             Arg::new("no-shadow")
                 .short('n')
@@ -39,50 +189,913 @@ fn main() {
                 .help("Use solid font without shadow (same as --font standard_solid)")
                 .action(clap::ArgAction::SetTrue)
         )
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .value_name("FORMAT")
+                .help("Output format")
+                .value_parser(clap::builder::PossibleValuesParser::new(OutputFormat::NAMES))
+                .default_value("text")
+        )
+        .arg(
+            Arg::new("full-page")
+                .long("full-page")
+                .help("Emit a complete HTML document instead of a <pre> block (html format)")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("color")
+                .long("color")
+                .value_name("COLOR")
+                .help("Letter color as #rrggbb or a name (html, svg, png formats)")
+                .value_parser(clap::value_parser!(Color))
+        )
+        .arg(
+            Arg::new("shadow-color")
+                .long("shadow-color")
+                .value_name("COLOR")
+                .help("Shadow color, defaults to the letter color (html, svg, png formats)")
+                .value_parser(clap::value_parser!(Color))
+        )
+        .arg(
+            Arg::new("background")
+                .long("background")
+                .value_name("COLOR")
+                .help("Background color, transparent when omitted (html, svg, png formats)")
+                .value_parser(clap::value_parser!(Color))
+        )
+        .arg(
+            Arg::new("cell-size")
+                .long("cell-size")
+                .value_name("WxH")
+                .help("Pixel size of one character cell (svg, png formats)")
+                .value_parser(|s: &str| s.parse::<CellSize>())
+                .default_value("8x16")
+        )
+        .arg(
+            Arg::new("ascii")
+                .long("ascii")
+                .help("Use plain ASCII (# + - |) instead of Unicode blocks and box drawing (default when the locale is not UTF-8)")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("unicode")
+                .long("unicode")
+                .help("Always use Unicode, even when the locale is not UTF-8")
+                .conflicts_with("ascii")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("ascii-fill")
+                .long("ascii-fill")
+                .value_name("CHAR")
+                .help("Character for filled blocks in ASCII mode [default: #]")
+                .value_parser(|s: &str| match s.parse::<char>() {
+                    Ok(ch) if ch.is_ascii() => Ok(ch),
+                    _ => Err(format!("'{}' is not a single ASCII character", s)),
+                })
+        )
+        .arg(
+            Arg::new("ascii-shadow")
+                .long("ascii-shadow")
+                .value_name("STYLE")
+                .help("Draw the shadow in ASCII mode with + - | (lines) or leave it out (none)")
+                .value_parser(["lines", "none"])
+                .default_value("lines")
+        )
+        .arg(
+            Arg::new("border")
+                .long("border")
+                .value_name("STYLE")
+                .help("Draw a frame around the output")
+                .value_parser(clap::builder::PossibleValuesParser::new(BorderStyle::NAMES))
+        )
+        .arg(
+            Arg::new("padding")
+                .long("padding")
+                .value_name("SPACING")
+                .help("Space inside the frame around the art, CSS-style: \"all\", \"v h\", \"t h b\" or \"t r b l\" [default: 0 1 with --border, else 0]")
+                .value_parser(|s: &str| s.parse::<Spacing>())
+        )
+        .arg(
+            Arg::new("margin")
+                .long("margin")
+                .value_name("SPACING")
+                .help("Space outside the frame, CSS-style like --padding")
+                .value_parser(|s: &str| s.parse::<Spacing>())
+        )
+        .arg(
+            Arg::new("fill")
+                .long("fill")
+                .value_name("CHAR")
+                .help("Character filling the padded area (e.g. ░) [default: space]")
+                .value_parser(clap::value_parser!(char))
+        )
+        .arg(
+            Arg::new("title")
+                .long("title")
+                .value_name("TEXT")
+                .help("Plain text title set into the top of the frame")
+                .value_parser(decorate::parse_label)
+                .requires("border")
+        )
+        .arg(
+            Arg::new("footer")
+                .long("footer")
+                .value_name("TEXT")
+                .help("Plain text footer set into the bottom of the frame")
+                .value_parser(decorate::parse_label)
+                .requires("border")
+        )
+        .arg(
+            Arg::new("comment")
+                .long("comment")
+                .value_name("STYLE")
+                .help("Wrap text output in a source code comment (//, #, --, /*, <!--, ;;, rem or a language name)")
+                .value_parser(clap::builder::PossibleValuesParser::new(CommentStyle::NAMES))
+                .ignore_case(true)
+                .hide_possible_values(true)
+        )
+        .arg(
+            Arg::new("comment-border")
+                .long("comment-border")
+                .help("Add a border line above and below the comment")
+                .requires("comment")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("comment-pad")
+                .long("comment-pad")
+                .help("Pad comment lines to the same width and close them on the right")
+                .requires("comment")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("literal-name")
+                .long("literal-name")
+                .value_name("NAME")
+                .help("Name of the constant holding the art (rust, c, python, go, shell, js formats)")
+                .default_value("BANNER")
+                .value_parser(literal::parse_name)
+        )
+        .arg(
+            Arg::new("escape-unicode")
+                .long("escape-unicode")
+                .help("Write non-ASCII characters as escape sequences (rust, c, python, go, shell, js formats)")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("output")
+                .short('o')
+                .long("output")
+                .value_name("FILE")
+                .help("Write output to FILE instead of stdout")
+        )
+        // Fonts converted on the fly, which --resolution and --add-shadow apply to
+        .group(ArgGroup::new("converted").args(["font-file", "ttf"]))
+        .args_conflicts_with_subcommands(true)
+        .subcommand(
+            Command::new("font")
+                .about("Tools for font designers")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("inspect")
+                        .about("Show every glyph of a font with its code point, inconsistent glyphs and Unicode coverage")
+                        .arg(
+                            Arg::new("font")
+                                .help("Font to inspect")
+                                .value_parser(clap::builder::PossibleValuesParser::new(font_names()))
+                        )
+                        .arg(
+                            Arg::new("font-file")
+                                .long("font-file")
+                                .value_name("FILE")
+                                .help("Inspect a .blf font file or a PSF or BDF bitmap font instead")
+                        )
+                        .group(ArgGroup::new("source").args(["font", "font-file"]).required(true))
+                        .arg(
+                            Arg::new("resolution")
+                                .long("resolution")
+                                .value_name("CELLS")
+                                .help("Pixels per cell when converting a bitmap font: one (full), one by two (half) or two by two (quadrant)")
+                                .value_parser(clap::builder::PossibleValuesParser::new(Resolution::NAMES))
+                                .default_value("half")
+                        )
+                        .arg(
+                            Arg::new("add-shadow")
+                                .long("add-shadow")
+                                .help("Give a converted bitmap font a drop shadow")
+                                .requires("font-file")
+                                .action(clap::ArgAction::SetTrue)
+                        )
+                        .arg(
+                            Arg::new("width")
+                                .short('w')
+                                .long("width")
+                                .value_name("WIDTH")
+                                .help("Maximum width of the character map")
+                                .value_parser(clap::value_parser!(u32).range(1..))
+                                .default_value("80")
+                        )
+                )
+                .subcommand(
+                    Command::new("check")
+                        .about("Validate font files, reporting every problem with its line number")
+                        .arg(
+                            Arg::new("files")
+                                .help("Font files to check")
+                                .value_name("FILE")
+                                .required(true)
+                                .num_args(1..)
+                        )
+                )
+                .subcommand(
+                    Command::new("convert")
+                        .about("Convert a PSF or BDF bitmap font into a .blf font file")
+                        .arg(
+                            Arg::new("input")
+                                .help("Font to convert, optionally gzip-compressed")
+                                .value_name("INPUT")
+                                .required(true)
+                        )
+                        .arg(
+                            Arg::new("output")
+                                .help(".blf font file to write")
+                                .value_name("OUTPUT")
+                                .required(true)
+                        )
+                        .arg(
+                            Arg::new("resolution")
+                                .long("resolution")
+                                .value_name("CELLS")
+                                .help("Pixels per cell: one (full), one by two (half) or two by two (quadrant)")
+                                .value_parser(clap::builder::PossibleValuesParser::new(Resolution::NAMES))
+                                .default_value("half")
+                        )
+                        .arg(
+                            Arg::new("add-shadow")
+                                .long("add-shadow")
+                                .help("Give every glyph a drop shadow")
+                                .action(clap::ArgAction::SetTrue)
+                        )
+                )
+                .subcommand(
+                    Command::new("edit")
+                        .about("Edit a font file in the terminal, creating it if it does not exist")
+                        .arg(
+                            Arg::new("file")
+                                .help("Font file to edit")
+                                .value_name("FILE")
+                                .required(true)
+                        )
+                        .arg(
+                            Arg::new("from")
+                                .long("from")
+                                .value_name("FONT")
+                                .help("Start a new font file as a copy of a registered font")
+                                .value_parser(clap::builder::PossibleValuesParser::new(font_names()))
+                        )
+                        .arg(
+                            Arg::new("height")
+                                .long("height")
+                                .value_name("ROWS")
+                                .help("Height of a new blank font")
+                                .value_parser(clap::value_parser!(u32).range(1..))
+                                .default_value("7")
+                                .conflicts_with("from")
+                        )
+                )
+        )
         .get_matches();
 
+    let result = match matches.subcommand() {
+        Some(("font", matches)) => run_font_command(matches),
+        _ => run(&matches),
+    };
+    if let Err(e) = result {
+        // A closed pipe (e.g. `blocklet ... | head`) is not worth reporting
+        if e.downcast_ref::<io::Error>().map(|e| e.kind()) == Some(io::ErrorKind::BrokenPipe) {
+            return;
+        }
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
+}
+
+fn run(matches: &ArgMatches) -> Result<()> {
     let texts: Vec<String> = matches
         .get_many::<String>("text")
         .map(|vals| vals.map(|s| s.to_string()).collect())
         .unwrap_or_default();
-    
-    if texts.is_empty() {
-        eprintln!("Error: Please provide text to render");
-        process::exit(1);
-    }
-    
+
     let width = *matches.get_one::<u32>("width").unwrap();
+    let line_gap = *matches.get_one::<i32>("line-gap").unwrap();
     let no_shadow = matches.get_flag("no-shadow");
-    
+    let format: OutputFormat = matches
+        .get_one::<String>("format")
+        .unwrap()
+        .parse()
+        .map_err(|e: String| anyhow!(e))?;
+
     // Determine font based on flags
-    let font_name = if no_shadow {
-        "standard_solid"
+    let font = match (
+        matches.get_one::<String>("font-file"),
+        matches.get_one::<String>("ttf"),
+    ) {
+        (Some(path), _) => Cow::Owned(load_font_file(Path::new(path), &import_options(matches)?)?),
+        (_, Some(path)) => {
+            let size = *matches.get_one::<u32>("size").unwrap() as usize;
+            Cow::Owned(load_ttf_font(
+                Path::new(path),
+                size,
+                &texts.join("\n"),
+                &import_options(matches)?,
+            )?)
+        }
+        _ => {
+            let font_name = if no_shadow {
+                "standard_solid"
+            } else {
+                matches.get_one::<String>("font").unwrap().as_str()
+            };
+            Cow::Borrowed(
+                get_font(font_name).context(format!("Failed to load font '{}'", font_name))?,
+            )
+        }
+    };
+    let font_name = font.name.clone();
+
+    // ASCII mode converts the font's glyphs, so every later stage sees plain ASCII
+    let ascii = if matches.get_flag("ascii") || matches.contains_id("ascii-fill") {
+        true
+    } else if matches.get_flag("unicode") {
+        false
     } else {
-        matches.get_one::<String>("font").unwrap().as_str()
+        format == OutputFormat::Text && !blocklet::ascii::locale_supports_unicode()
     };
-    
-    // Determine height based on font
-    let height = match font_name {
-        "standard_shadow" | "standard" | "standard_solid" => 7,
-        _ => 7, // default
+    let font = derive_font(font, matches, ascii)?;
+    let options = RenderOptions {
+        font_name,
+        max_width: width,
+        height: font.height,
+        spacing: 1,
+        line_gap,
+        preserve_whitespace: matches.get_flag("preserve-whitespace"),
+        tab_width: *matches.get_one::<u32>("tab-width").unwrap(),
+        direction: matches
+            .get_one::<String>("direction")
+            .unwrap()
+            .parse()
+            .map_err(|e: String| anyhow!(e))?,
     };
 
-    // Render each text argument as a separate line
-    let mut first = true;
-    for text in texts {
-        if !first {
-            println!(); // Add blank line between outputs
+    if matches.get_flag("list-fonts") {
+        return list_fonts(&mut io::stdout().lock());
+    }
+    if let Some(text) = matches.get_one::<String>("showcase") {
+        return showcase(&mut io::stdout().lock(), text, &options, matches, ascii);
+    }
+    if texts.is_empty() {
+        return Err(anyhow!("Please provide text to render"));
+    }
+
+    let mut renderer = Renderer::with_font(&font, options);
+
+    let comment = matches
+        .get_one::<String>("comment")
+        .map(|style| style.parse::<CommentStyle>().map_err(|e| anyhow!(e)))
+        .transpose()?;
+    if comment.is_some() && format != OutputFormat::Text {
+        return Err(anyhow!("--comment only applies to text output"));
+    }
+
+    let post_processed = post_process_arg(matches);
+    // The JSON layout describes rows and lines as rendered, which rotating or framing would break
+    if let (OutputFormat::Json, Some(id)) = (format, post_processed) {
+        return Err(anyhow!("--{} does not apply to JSON output", id));
+    }
+
+    let output = matches.get_one::<String>("output");
+    if output.is_none() && format.is_binary() && io::stdout().is_terminal() {
+        return Err(anyhow!(
+            "Refusing to write {} data to a terminal, use --output FILE",
+            format
+        ));
+    }
+
+    // Output for a file is produced in memory first, so a failure never
+    // leaves an empty or truncated file behind
+    let mut buffer = Vec::new();
+    let mut stdout;
+    let mut out: &mut dyn Write = if output.is_some() {
+        &mut buffer
+    } else {
+        stdout = BufWriter::new(io::stdout().lock());
+        &mut stdout
+    };
+
+    match format {
+        OutputFormat::Text if comment.is_none() && post_processed.is_none() => {
+            // Stream each argument straight to stdout
+            let result = render_lines(&mut renderer, &mut out, &texts);
+            out.flush()?;
+            result?;
+            writeln!(out)?; // Final newline
+        }
+        OutputFormat::Json => write_json(&mut renderer, &mut out, &texts)?,
+        format => {
+            let mut art = Vec::new();
+            render_lines(&mut renderer, &mut art, &texts)?;
+            let art = String::from_utf8(art)?;
+            let art = apply_transforms(art, matches)?;
+            let art = decorate(art, matches, ascii);
+            let foreground = matches.get_one::<Color>("color").copied();
+            let shadow = matches.get_one::<Color>("shadow-color").copied();
+            let background = matches.get_one::<Color>("background").copied();
+            let cell_size = *matches.get_one::<CellSize>("cell-size").unwrap();
+
+            match format {
+                OutputFormat::Text => match comment {
+                    Some(style) => {
+                        let options = CommentOptions {
+                            style,
+                            border: matches.get_flag("comment-border"),
+                            pad: matches.get_flag("comment-pad"),
+                        };
+                        out.write_all(comment::to_comment(&art, &options).as_bytes())?;
+                    }
+                    None => writeln!(out, "{}", art)?,
+                },
+                OutputFormat::Html => {
+                    let options = HtmlOptions {
+                        full_page: matches.get_flag("full-page"),
+                        foreground,
+                        shadow,
+                        background,
+                    };
+                    out.write_all(html::to_html(&art, &options).as_bytes())?;
+                }
+                OutputFormat::Svg => {
+                    let options = ImageOptions {
+                        cell_size,
+                        foreground,
+                        shadow,
+                        background,
+                    };
+                    out.write_all(svg::to_svg(&art, &options).as_bytes())?;
+                }
+                OutputFormat::Png => {
+                    let options = ImageOptions {
+                        cell_size,
+                        foreground,
+                        shadow,
+                        background,
+                    };
+                    png::write_png(&art, &options, &mut out)?;
+                }
+                OutputFormat::Literal(language) => {
+                    let options = LiteralOptions {
+                        name: matches.get_one::<String>("literal-name").unwrap().clone(),
+                        escape_unicode: matches.get_flag("escape-unicode"),
+                    };
+                    out.write_all(literal::to_literal(&art, language, &options).as_bytes())?;
+                }
+                OutputFormat::Json => unreachable!("handled above"),
+            }
+        }
+    }
+
+    out.flush()?;
+    if let Some(path) = output {
+        let mut file =
+            File::create(path).with_context(|| format!("Failed to create '{}'", path))?;
+        file.write_all(&buffer)
+            .with_context(|| format!("Failed to write '{}'", path))?;
+    }
+    Ok(())
+}
+
+fn run_font_command(matches: &ArgMatches) -> Result<()> {
+    match matches.subcommand() {
+        Some(("inspect", matches)) => {
+            let font = match matches.get_one::<String>("font-file") {
+                Some(path) => {
+                    Cow::Owned(load_font_file(Path::new(path), &import_options(matches)?)?)
+                }
+                None => {
+                    let name = matches.get_one::<String>("font").unwrap();
+                    Cow::Borrowed(
+                        get_font(name).context(format!("Failed to load font '{}'", name))?,
+                    )
+                }
+            };
+            let width = *matches.get_one::<u32>("width").unwrap() as usize;
+            inspect_font(&mut io::stdout().lock(), &font, width)
+        }
+        Some(("check", matches)) => check_font_files(
+            &mut io::stdout().lock(),
+            matches.get_many::<String>("files").unwrap(),
+        ),
+        Some(("convert", matches)) => {
+            let input = Path::new(matches.get_one::<String>("input").unwrap());
+            let output = matches.get_one::<String>("output").unwrap();
+            let font = load_font_file(input, &import_options(matches)?)?;
+            std::fs::write(output, fontfile::write(&font))
+                .with_context(|| format!("Failed to write '{}'", output))?;
+            Ok(())
         }
-        first = false;
-        
-        match renderer::render_text(&text, font_name, width, height) {
-            Ok(output) => print!("{}", output),
+        Some(("edit", matches)) => edit_font(matches),
+        _ => unreachable!("subcommand required by clap"),
+    }
+}
+
+/// Load a .blf font file, or convert a font in another format
+fn load_font_file(path: &Path, options: &ImportOptions) -> Result<Font> {
+    let data =
+        std::fs::read(path).with_context(|| format!("Failed to read '{}'", path.display()))?;
+    let source = FontSource::File(path.to_path_buf());
+    match import::import(&data, &import::font_name(path), options, source.clone()) {
+        #[cfg(feature = "ttf")]
+        Err(ImportError::UnknownFormat) if import::ttf::is_ttf(&data) => Err(anyhow!(
+            "'{}' is an outline font, load it with --ttf",
+            path.display()
+        )),
+        Err(ImportError::UnknownFormat) => {
+            let text = String::from_utf8(data)
+                .map_err(|_| anyhow!("'{}' is not a font blocklet can read", path.display()))?;
+            // Include every problem, not just the context, so the file can be fixed in one go
+            fontfile::parse(&text, source).map_err(|errors| {
+                anyhow!(
+                    "Failed to load font file '{}': {}",
+                    path.display(),
+                    LoadError::Invalid(errors)
+                )
+            })
+        }
+        result => result.map_err(|e| anyhow!("Failed to convert '{}': {}", path.display(), e)),
+    }
+}
+
+/// Rasterise the glyphs needed to render `text` from a TrueType or OpenType
+/// font `size` pixels tall
+#[cfg(feature = "ttf")]
+fn load_ttf_font(path: &Path, size: usize, text: &str, options: &ImportOptions) -> Result<Font> {
+    let data =
+        std::fs::read(path).with_context(|| format!("Failed to read '{}'", path.display()))?;
+    let source = FontSource::File(path.to_path_buf());
+    import::ttf::import(
+        &data,
+        &import::font_name(path),
+        size,
+        import::ttf::characters_for(text),
+        options,
+        source,
+    )
+    .map_err(|e| anyhow!("Failed to rasterise '{}': {}", path.display(), e))
+}
+
+#[cfg(not(feature = "ttf"))]
+fn load_ttf_font(
+    _path: &Path,
+    _size: usize,
+    _text: &str,
+    _options: &ImportOptions,
+) -> Result<Font> {
+    Err(anyhow!(
+        "Outline fonts require blocklet to be built with the 'ttf' feature"
+    ))
+}
+
+fn import_options(matches: &ArgMatches) -> Result<ImportOptions> {
+    Ok(ImportOptions {
+        resolution: matches
+            .get_one::<String>("resolution")
+            .unwrap()
+            .parse()
+            .map_err(|e: String| anyhow!(e))?,
+        shadow: matches.get_flag("add-shadow"),
+    })
+}
+
+/// Print every problem in each font file as `FILE:LINE: message`, failing if
+/// any file has problems
+fn check_font_files<'a, W: Write>(
+    out: &mut W,
+    paths: impl Iterator<Item = &'a String>,
+) -> Result<()> {
+    let mut problems = 0;
+    for path in paths {
+        // An unreadable file is one more problem, not a reason to skip the rest
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
             Err(e) => {
-                eprintln!("Error: {}", e);
-                process::exit(1);
+                writeln!(out, "{}: failed to read: {}", path, e)?;
+                problems += 1;
+                continue;
             }
+        };
+        let errors = fontfile::check(&text);
+        for error in &errors {
+            match error.line {
+                Some(line) => writeln!(out, "{}:{}: {}", path, line, error.kind)?,
+                None => writeln!(out, "{}: {}", path, error.kind)?,
+            }
+        }
+        if errors.is_empty() {
+            writeln!(out, "{}: ok", path)?;
         }
+        problems += errors.len();
     }
-    
-    println!(); // Final newline
+    if problems > 0 {
+        out.flush()?;
+        return Err(anyhow!(
+            "{} problem{} found",
+            problems,
+            if problems == 1 { "" } else { "s" }
+        ));
+    }
+    Ok(())
+}
+
+/// Open the font editor on a font file, starting a new font when the file
+/// does not exist yet
+#[cfg(feature = "editor")]
+fn edit_font(matches: &ArgMatches) -> Result<()> {
+    use blocklet::editor::{self, Editor};
+    use std::path::PathBuf;
+
+    let path = PathBuf::from(matches.get_one::<String>("file").unwrap());
+    let from = matches.get_one::<String>("from");
+    let font = if path.exists() {
+        if from.is_some() {
+            return Err(anyhow!(
+                "'{}' already exists, leave out --from to edit it",
+                path.display()
+            ));
+        }
+        fontfile::load(&path)
+            .map_err(|e| anyhow!("Failed to load font file '{}': {}", path.display(), e))?
+    } else {
+        let name = path
+            .file_stem()
+            .map_or("font".into(), |stem| stem.to_string_lossy());
+        match from {
+            Some(from) => Font {
+                name: name.into_owned(),
+                source: FontSource::File(path.clone()),
+                ..get_font(from)?.clone()
+            },
+            None => editor::blank_font(
+                &name,
+                *matches.get_one::<u32>("height").unwrap(),
+                path.clone(),
+            ),
+        }
+    };
+
+    if !io::stdout().is_terminal() {
+        return Err(anyhow!("The font editor needs a terminal"));
+    }
+    let mut editor = Editor::new(font, path);
+    editor::run(&mut editor)?;
+    Ok(())
+}
+
+#[cfg(not(feature = "editor"))]
+fn edit_font(_matches: &ArgMatches) -> Result<()> {
+    Err(anyhow!(
+        "The font editor requires blocklet to be built with the 'editor' feature"
+    ))
+}
+
+/// Print a font's character map, the glyphs whose dimensions are
+/// inconsistent and the Unicode blocks it covers
+fn inspect_font<W: Write>(out: &mut W, font: &Font, width: usize) -> Result<()> {
+    writeln!(out, "{}", font.name)?;
+    writeln!(out, "    {}", font.description)?;
+    writeln!(out, "    Height: {} rows", font.height)?;
+    writeln!(out, "    Glyphs: {}", font.characters.len())?;
+    writeln!(out, "    Source: {}", font.source)?;
+    writeln!(out)?;
+    writeln!(out, "{}", inspect::character_map(font, width))?;
+
+    writeln!(out)?;
+    let issues = inspect::glyph_issues(font);
+    if issues.is_empty() {
+        writeln!(out, "Inconsistent glyphs: none")?;
+    } else {
+        writeln!(out, "Inconsistent glyphs:")?;
+        for (ch, issue) in issues {
+            writeln!(out, "    U+{:04X} {}: {}", ch as u32, ch, issue)?;
+        }
+    }
+
+    writeln!(out)?;
+    writeln!(out, "Unicode blocks:")?;
+    for block in inspect::block_coverage(font) {
+        let status = if block.is_complete() {
+            "complete"
+        } else {
+            "partial"
+        };
+        writeln!(
+            out,
+            "    U+{:04X}..U+{:04X} {}: {} of {} ({})",
+            block.start, block.end, block.name, block.covered, block.total, status
+        )?;
+    }
+    Ok(())
+}
+
+/// Arguments that change the rendered art after layout, so it cannot be streamed
+const POST_PROCESS_ARGS: &[&str] = &[
+    "rotate", "flip-h", "flip-v", "border", "padding", "margin", "fill",
+];
+
+/// The first post-processing argument given on the command line
+fn post_process_arg(matches: &ArgMatches) -> Option<&'static str> {
+    POST_PROCESS_ARGS
+        .iter()
+        .copied()
+        .find(|id| matches.value_source(id) == Some(ValueSource::CommandLine))
+}
+
+/// Apply the font arguments to a registered font: the shadow direction, then
+/// scaling so strokes are extended, then ASCII conversion of the result
+fn derive_font(
+    mut font: Cow<'static, Font>,
+    matches: &ArgMatches,
+    ascii: bool,
+) -> Result<Cow<'static, Font>> {
+    let shadow_direction: ShadowDirection = matches
+        .get_one::<String>("shadow-direction")
+        .unwrap()
+        .parse()
+        .map_err(|e: String| anyhow!(e))?;
+    if shadow_direction == ShadowDirection::Left {
+        font = Cow::Owned(transform::left_shadow_font(&font));
+    }
+
+    let scale = matches.get_one::<u32>("scale").copied().unwrap_or(1);
+    let scale_x = matches.get_one::<u32>("scale-x").copied().unwrap_or(scale) as usize;
+    let scale_y = matches.get_one::<u32>("scale-y").copied().unwrap_or(scale) as usize;
+    if scale_x > 1 || scale_y > 1 {
+        font = Cow::Owned(transform::scale_font(&font, scale_x, scale_y));
+    }
+
+    if ascii {
+        let ascii_options = AsciiOptions {
+            fill: matches
+                .get_one::<char>("ascii-fill")
+                .copied()
+                .unwrap_or('#'),
+            shadow: matches
+                .get_one::<String>("ascii-shadow")
+                .unwrap()
+                .parse()
+                .map_err(|e: String| anyhow!(e))?,
+        };
+        font = Cow::Owned(blocklet::ascii::ascii_font(&font, &ascii_options));
+    }
+    Ok(font)
+}
+
+/// Print every registered font with its description, height, coverage and source
+fn list_fonts<W: Write>(out: &mut W) -> Result<()> {
+    for (name, font) in fonts() {
+        if name != font.name {
+            writeln!(out, "{} (alias of {})", name, font.name)?;
+            continue;
+        }
+        writeln!(out, "{}", name)?;
+        writeln!(out, "    {}", font.description)?;
+        writeln!(out, "    Height:   {} rows", font.height)?;
+        writeln!(
+            out,
+            "    Coverage: {} characters ({})",
+            font.characters.len(),
+            format_coverage(font)
+        )?;
+        writeln!(out, "    Source:   {}", font.source)?;
+    }
+    Ok(())
+}
+
+/// Describe a font's coverage as space-separated characters and ranges,
+/// e.g. `space ! 0-9 A-Z`
+fn format_coverage(font: &Font) -> String {
+    let name = |ch: char| match ch {
+        ' ' => "space".to_string(),
+        _ if ch.is_control() || ch.is_whitespace() => format!("U+{:04X}", ch as u32),
+        _ => ch.to_string(),
+    };
+    let mut items = Vec::new();
+    for run in font.coverage() {
+        let (start, end) = (*run.start(), *run.end());
+        if end as u32 - start as u32 >= 2 {
+            items.push(format!("{}-{}", name(start), name(end)));
+        } else {
+            items.extend(run.map(name));
+        }
+    }
+    items.join(" ")
+}
+
+/// Render `text` in every registered font under its name, with the same
+/// layout and font arguments as normal output
+fn showcase<W: Write>(
+    out: &mut W,
+    text: &str,
+    options: &RenderOptions,
+    matches: &ArgMatches,
+    ascii: bool,
+) -> Result<()> {
+    let fonts = fonts()
+        .into_iter()
+        .filter(|(name, font)| *name == font.name); // Skip aliases
+    for (i, (name, font)) in fonts.enumerate() {
+        if i > 0 {
+            writeln!(out)?;
+        }
+        writeln!(out, "{}", name)?;
+        let font = derive_font(Cow::Borrowed(font), matches, ascii)?;
+        let options = RenderOptions {
+            font_name: name.to_string(),
+            height: font.height,
+            ..options.clone()
+        };
+        Renderer::with_font(&font, options).render_to(out, text)?;
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+/// Apply the rotation and then the flip arguments to rendered art
+fn apply_transforms(mut art: String, matches: &ArgMatches) -> Result<String> {
+    if let Some(degrees) = matches.get_one::<String>("rotate") {
+        art = transform::rotate(&art, degrees.parse().map_err(|e: String| anyhow!(e))?);
+    }
+    if matches.get_flag("flip-h") {
+        art = transform::flip_horizontal(&art);
+    }
+    if matches.get_flag("flip-v") {
+        art = transform::flip_vertical(&art);
+    }
+    Ok(art)
+}
+
+/// Apply the decoration arguments to rendered art: padding, then the frame,
+/// then the margin
+fn decorate(mut art: String, matches: &ArgMatches, ascii: bool) -> String {
+    let border = matches.get_one::<String>("border");
+
+    let padding = match matches.get_one::<Spacing>("padding") {
+        Some(padding) => *padding,
+        None if border.is_some() => Spacing::new(0, 1, 0, 1),
+        None => Spacing::default(),
+    };
+    let fill = matches.get_one::<char>("fill").copied();
+    if !padding.is_zero() || fill.is_some() {
+        let fill = fill.unwrap_or(' ');
+        art = decorate::pad(
+            &art,
+            padding,
+            if ascii && !fill.is_ascii() { '.' } else { fill },
+        );
+    }
+
+    if let Some(style) = border {
+        let options = FrameOptions {
+            style: style.parse().expect("validated by clap"),
+            title: matches.get_one::<String>("title").cloned(),
+            footer: matches.get_one::<String>("footer").cloned(),
+            ascii,
+        };
+        art = decorate::frame(&art, &options);
+    }
+
+    if let Some(margin) = matches.get_one::<Spacing>("margin") {
+        art = decorate::pad(&art, *margin, ' ');
+    }
+    art
+}
+
+/// Write the rendered rows and their layout as JSON
+#[cfg(feature = "serde")]
+fn write_json<W: Write>(renderer: &mut Renderer, out: &mut W, texts: &[String]) -> Result<()> {
+    let rendered = renderer.render_grid(texts)?;
+    writeln!(out, "{}", blocklet::output::json::to_json(&rendered)?)?;
+    Ok(())
+}
+
+#[cfg(not(feature = "serde"))]
+fn write_json<W: Write>(_renderer: &mut Renderer, _out: &mut W, _texts: &[String]) -> Result<()> {
+    Err(anyhow!(
+        "JSON output requires blocklet to be built with the 'serde' feature"
+    ))
+}
+
+/// Render each text argument as a separate line
+fn render_lines<W: Write>(renderer: &mut Renderer, out: &mut W, texts: &[String]) -> Result<()> {
+    renderer.render_all_to(out, texts)
 }
@@ -0,0 +1,292 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Comment syntaxes that rendered art can be wrapped in
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CommentStyle {
+    /// `//` (C, C++, Rust, Go, Java, JavaScript)
+    Slash,
+    /// `#` (shell, Python, Ruby, YAML, TOML)
+    Hash,
+    /// `--` (SQL, Lua, Haskell)
+    Dash,
+    /// `/* */` (C, CSS)
+    Block,
+    /// `<!-- -->` (HTML, XML, Markdown)
+    Markup,
+    /// `;;` (Lisp, Clojure, assembly)
+    Lisp,
+    /// `REM` (batch files)
+    Rem,
+}
+
+impl CommentStyle {
+    /// Style names and language aliases accepted by `--comment`
+    pub const NAMES: &'static [&'static str] = &[
+        "//", "#", "--", "/*", "<!--", ";;", "rem", "slash", "hash", "dash", "block", "markup",
+        "lisp", "c", "cpp", "rust", "go", "java", "js", "ts", "sh", "bash", "python", "ruby",
+        "yaml", "toml", "sql", "lua", "haskell", "css", "html", "xml", "markdown", "clojure",
+        "elisp", "asm", "bat", "cmd",
+    ];
+
+    /// The opening and (for block comments) closing delimiters
+    pub fn delimiters(self) -> (&'static str, Option<&'static str>) {
+        match self {
+            CommentStyle::Slash => ("//", None),
+            CommentStyle::Hash => ("#", None),
+            CommentStyle::Dash => ("--", None),
+            CommentStyle::Block => ("/*", Some("*/")),
+            CommentStyle::Markup => ("<!--", Some("-->")),
+            CommentStyle::Lisp => (";;", None),
+            CommentStyle::Rem => ("REM", None),
+        }
+    }
+}
+
+impl FromStr for CommentStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let style = match s.to_ascii_lowercase().as_str() {
+            "//" | "slash" | "c" | "cpp" | "rust" | "go" | "java" | "js" | "ts" => {
+                CommentStyle::Slash
+            }
+            "#" | "hash" | "sh" | "bash" | "python" | "ruby" | "yaml" | "toml" => {
+                CommentStyle::Hash
+            }
+            "--" | "dash" | "sql" | "lua" | "haskell" => CommentStyle::Dash,
+            "/*" | "/* */" | "block" | "css" => CommentStyle::Block,
+            "<!--" | "<!-- -->" | "markup" | "html" | "xml" | "markdown" => CommentStyle::Markup,
+            ";;" | "lisp" | "clojure" | "elisp" | "asm" => CommentStyle::Lisp,
+            "rem" | "bat" | "cmd" => CommentStyle::Rem,
+            _ => return Err(format!("Unknown comment style '{}'", s)),
+        };
+        Ok(style)
+    }
+}
+
+impl fmt::Display for CommentStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.delimiters() {
+            (open, Some(close)) => write!(f, "{} {}", open, close),
+            (open, None) => f.write_str(open),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct CommentOptions {
+    pub style: CommentStyle,
+    /// Add a rule line above and below the art
+    pub border: bool,
+    /// Pad every line to the same width and close it on the right, so the
+    /// comment forms a box
+    pub pad: bool,
+}
+
+impl Default for CommentOptions {
+    fn default() -> Self {
+        Self {
+            style: CommentStyle::Slash,
+            border: false,
+            pad: false,
+        }
+    }
+}
+
+/// Wrap rendered art in comment syntax so it can be pasted into source code.
+///
+/// Line comments prefix every row; block comments open and close every row so
+/// the result stays valid wherever it is placed. Trailing spaces are trimmed
+/// unless `pad` is set.
+pub fn to_comment(art: &str, options: &CommentOptions) -> String {
+    let (open, close) = options.style.delimiters();
+    // Escaping can widen a line, so padding is measured after it
+    let bodies: Vec<String> = art
+        .lines()
+        .map(|line| escape_body(line, options.style))
+        .collect();
+    let width = bodies
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut lines: Vec<String> = bodies
+        .iter()
+        .map(|line| {
+            let mut out = format!("{} {}", open, line);
+            if options.pad {
+                out.extend(std::iter::repeat_n(' ', width - line.chars().count()));
+                match close {
+                    Some(close) => out.push_str(&format!(" {}", close)),
+                    // Mirror symmetric line comments on the right
+                    None if options.style != CommentStyle::Rem => {
+                        out.push_str(&format!(" {}", open))
+                    }
+                    None => {}
+                }
+            } else {
+                let trimmed = out.trim_end().len();
+                out.truncate(trimmed);
+                if let Some(close) = close {
+                    out.push_str(&format!(" {}", close));
+                }
+            }
+            out
+        })
+        .collect();
+
+    if options.border {
+        let total = lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        let rule = rule_line(options.style, total);
+        lines.insert(0, rule.clone());
+        lines.push(rule);
+    }
+
+    let mut out = lines.join("\n");
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    out
+}
+
+/// Break up sequences that would open or close a block comment early, so
+/// the comment stays valid ASCII. XML and HTML forbid `--` anywhere in a
+/// comment, which `--ascii` shadows are full of, so a space is put between
+/// every two dashes of a run, and `*/` and `/*` become `* /` and `/ *`.
+fn escape_body(line: &str, style: CommentStyle) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut previous = None;
+    for ch in line.chars() {
+        let split = matches!(
+            (style, previous, ch),
+            (CommentStyle::Markup, Some('-'), '-')
+                | (CommentStyle::Block, Some('*'), '/')
+                | (CommentStyle::Block, Some('/'), '*')
+        );
+        if split {
+            out.push(' ');
+        }
+        out.push(ch);
+        previous = Some(ch);
+    }
+    out
+}
+
+/// A border line `width` characters wide made from the comment delimiters
+fn rule_line(style: CommentStyle, width: usize) -> String {
+    let (open, close) = style.delimiters();
+    let fill = match style {
+        // Delimiters made of one repeated character fill the whole rule
+        CommentStyle::Slash | CommentStyle::Hash | CommentStyle::Dash | CommentStyle::Lisp => {
+            let ch = open.chars().next().unwrap();
+            return std::iter::repeat_n(ch, width.max(open.len())).collect();
+        }
+        CommentStyle::Block => '*',
+        CommentStyle::Markup | CommentStyle::Rem => '=',
+    };
+    let close = close.map(|c| format!(" {}", c)).unwrap_or_default();
+    let inner = width.saturating_sub(open.len() + 1 + close.len()).max(1);
+    format!("{} {}{}", open, fill.to_string().repeat(inner), close)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_styles() {
+        assert_eq!("rust".parse::<CommentStyle>().unwrap(), CommentStyle::Slash);
+        assert_eq!("#".parse::<CommentStyle>().unwrap(), CommentStyle::Hash);
+        assert_eq!(
+            "<!-- -->".parse::<CommentStyle>().unwrap(),
+            CommentStyle::Markup
+        );
+        assert_eq!("REM".parse::<CommentStyle>().unwrap(), CommentStyle::Rem);
+        assert!("fortran".parse::<CommentStyle>().is_err());
+        for name in CommentStyle::NAMES {
+            assert!(name.parse::<CommentStyle>().is_ok(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_line_comment() {
+        let options = CommentOptions {
+            style: CommentStyle::Hash,
+            ..CommentOptions::default()
+        };
+        assert_eq!(to_comment("██╗ \n    ", &options), "# ██╗\n#\n");
+    }
+
+    #[test]
+    fn test_block_comment_padded() {
+        let options = CommentOptions {
+            style: CommentStyle::Block,
+            border: false,
+            pad: true,
+        };
+        assert_eq!(to_comment("██\n█", &options), "/* ██ */\n/* █  */\n");
+    }
+
+    #[test]
+    fn test_escape_closing_delimiters() {
+        let options = CommentOptions {
+            style: CommentStyle::Markup,
+            border: false,
+            pad: true,
+        };
+        let comment = to_comment("##+---##|\n##|", &options);
+        assert_eq!(comment, "<!-- ##+- - -##| -->\n<!-- ##|         -->\n");
+        // Only the delimiters may contain "--"
+        for line in comment.lines() {
+            assert!(
+                !line["<!--".len()..line.len() - "-->".len()].contains("--"),
+                "{}",
+                line
+            );
+        }
+
+        let options = CommentOptions {
+            style: CommentStyle::Block,
+            ..options
+        };
+        assert_eq!(to_comment("**/", &options), "/* ** / */\n");
+        assert_eq!(
+            to_comment("/**/\n█", &options),
+            "/* / ** / */\n/* █      */\n"
+        );
+        assert!(to_comment("+--*/*--+", &options).is_ascii());
+    }
+
+    #[test]
+    fn test_border() {
+        let options = CommentOptions {
+            style: CommentStyle::Slash,
+            border: true,
+            pad: true,
+        };
+        assert_eq!(to_comment("AB", &options), "////////\n// AB //\n////////\n");
+
+        let options = CommentOptions {
+            style: CommentStyle::Markup,
+            border: true,
+            pad: true,
+        };
+        assert_eq!(
+            to_comment("AB", &options),
+            "<!-- == -->\n<!-- AB -->\n<!-- == -->\n"
+        );
+
+        let options = CommentOptions {
+            style: CommentStyle::Rem,
+            border: true,
+            pad: false,
+        };
+        assert_eq!(to_comment("AB", &options), "REM ==\nREM AB\nREM ==\n");
+    }
+}
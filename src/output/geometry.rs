@@ -0,0 +1,382 @@
+//! Geometric shapes for the characters used in block art.
+//!
+//! Graphical backends draw every cell as rectangles instead of text, so block
+//! elements and box-drawing strokes line up exactly no matter which fonts are
+//! installed on the machine displaying the result.
+
+use crate::boxdraw::{box_arms, is_arc, Arms, Weight, DOWN, LEFT, RIGHT, UP};
+
+/// An axis-aligned rectangle in pixels, relative to the top-left of the art
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    /// Coverage in `0.0..=1.0`, used for the shade characters
+    pub opacity: f32,
+}
+
+impl Rect {
+    fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+            opacity: 1.0,
+        }
+    }
+}
+
+/// Append the rectangles that draw `ch` in the cell at (`x`, `y`) of size
+/// `width` x `height`. Returns `false` when the character has no geometric
+/// form (letters, punctuation), leaving the caller to decide how to draw it.
+pub fn cell_rects(
+    ch: char,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    rects: &mut Vec<Rect>,
+) -> bool {
+    let (w, h) = (width, height);
+    let (hw, hh) = (w / 2.0, h / 2.0);
+    let mut push =
+        |rx: f32, ry: f32, rw: f32, rh: f32| rects.push(Rect::new(x + rx, y + ry, rw, rh));
+
+    match ch {
+        ' ' => {}
+        '█' => push(0.0, 0.0, w, h),
+        '▀' => push(0.0, 0.0, w, hh),
+        '▄' => push(0.0, hh, w, hh),
+        '▌' => push(0.0, 0.0, hw, h),
+        '▐' => push(hw, 0.0, hw, h),
+        // Lower eighth blocks
+        '▁'..='▇' => {
+            let eighths = (ch as u32 - '▁' as u32 + 1) as f32;
+            let bar = h * eighths / 8.0;
+            push(0.0, h - bar, w, bar);
+        }
+        // Left eighth blocks
+        '▉'..='▏' => {
+            let eighths = (8 - (ch as u32 - '▉' as u32 + 1)) as f32;
+            push(0.0, 0.0, w * eighths / 8.0, h);
+        }
+        '▔' => push(0.0, 0.0, w, h / 8.0),
+        '▕' => push(w * 7.0 / 8.0, 0.0, w / 8.0, h),
+        '░' | '▒' | '▓' => {
+            let opacity = match ch {
+                '░' => 0.25,
+                '▒' => 0.5,
+                _ => 0.75,
+            };
+            rects.push(Rect {
+                opacity,
+                ..Rect::new(x, y, w, h)
+            });
+        }
+        _ => {
+            if let Some(quadrants) = quadrants(ch) {
+                // Upper left, upper right, lower left, lower right
+                let corners = [(0.0, 0.0), (hw, 0.0), (0.0, hh), (hw, hh)];
+                for (filled, (qx, qy)) in quadrants.iter().zip(corners) {
+                    if *filled {
+                        push(qx, qy, hw, hh);
+                    }
+                }
+            } else if let Some(arms) = box_arms(ch) {
+                if is_arc(ch) {
+                    arc_rects(arms, x, y, w, h, rects);
+                } else {
+                    box_rects(arms, x, y, w, h, rects);
+                }
+            } else {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// Join rectangles that continue each other on the same row (such as runs of
+/// `█`), keeping the output of vector backends small
+pub fn merge_runs(rects: &mut Vec<Rect>) {
+    let mut merged: Vec<Rect> = Vec::with_capacity(rects.len());
+    for rect in rects.drain(..) {
+        if let Some(last) = merged.last_mut() {
+            if last.y == rect.y
+                && last.height == rect.height
+                && last.opacity == rect.opacity
+                && last.x + last.width == rect.x
+            {
+                last.width += rect.width;
+                continue;
+            }
+        }
+        merged.push(rect);
+    }
+    *rects = merged;
+}
+
+/// Filled quadrants (upper left, upper right, lower left, lower right)
+fn quadrants(ch: char) -> Option<[bool; 4]> {
+    let q = match ch {
+        '▘' => [true, false, false, false],
+        '▝' => [false, true, false, false],
+        '▖' => [false, false, true, false],
+        '▗' => [false, false, false, true],
+        '▚' => [true, false, false, true],
+        '▞' => [false, true, true, false],
+        '▛' => [true, true, true, false],
+        '▜' => [true, true, false, true],
+        '▙' => [true, false, true, true],
+        '▟' => [false, true, true, true],
+        _ => return None,
+    };
+    Some(q)
+}
+
+/// Draw the strokes of a box-drawing character.
+///
+/// Light and heavy arms are single strokes from the centre to the edge. Double
+/// arms are two parallel strokes whose ends meet at the inner or outer corner,
+/// the way a terminal font joins `╔`, `╦` and `╬`.
+fn box_rects(arms: Arms, x: f32, y: f32, w: f32, h: f32, rects: &mut Vec<Rect>) {
+    let unit = w.min(h);
+    let light = (unit / 8.0).max(1.0);
+    let heavy = light * 2.0;
+    let (cx, cy) = (w / 2.0, h / 2.0);
+
+    // Single strokes
+    for (side, weight) in arms.iter().enumerate() {
+        let t = match weight {
+            Weight::Light => light,
+            Weight::Heavy => heavy,
+            _ => continue,
+        };
+        let r = match side {
+            LEFT => Rect::new(0.0, cy - t / 2.0, cx + t / 2.0, t),
+            RIGHT => Rect::new(cx - t / 2.0, cy - t / 2.0, w - cx + t / 2.0, t),
+            UP => Rect::new(cx - t / 2.0, 0.0, t, cy + t / 2.0),
+            _ => Rect::new(cx - t / 2.0, cy - t / 2.0, t, h - cy + t / 2.0),
+        };
+        rects.push(Rect::new(x + r.x, y + r.y, r.width, r.height));
+    }
+
+    // Double strokes: the horizontal pair, then the vertical pair with the
+    // axes swapped
+    let double = |side: usize| arms[side] == Weight::Double;
+    let gap = (unit / 4.0).max(light);
+    let (top, bottom) = double_lines(
+        [double(LEFT), double(RIGHT), double(UP), double(DOWN)],
+        cx,
+        w,
+        gap,
+    );
+    for (offset, spans) in [(-gap, top), (gap, bottom)] {
+        for (start, end) in spans.into_iter().map(|span| overlap_corner(span, w, light)) {
+            rects.push(Rect::new(
+                x + start,
+                y + cy + offset - light / 2.0,
+                end - start,
+                light,
+            ));
+        }
+    }
+    let (left, right) = double_lines(
+        [double(UP), double(DOWN), double(LEFT), double(RIGHT)],
+        cy,
+        h,
+        gap,
+    );
+    for (offset, spans) in [(-gap, left), (gap, right)] {
+        for (start, end) in spans.into_iter().map(|span| overlap_corner(span, h, light)) {
+            rects.push(Rect::new(
+                x + cx + offset - light / 2.0,
+                y + start,
+                light,
+                end - start,
+            ));
+        }
+    }
+}
+
+/// Draw a rounded corner: a quarter circle joining its two arms, traced
+/// with small squares, and straight strokes from the circle to the edges.
+fn arc_rects(arms: Arms, x: f32, y: f32, w: f32, h: f32, rects: &mut Vec<Rect>) {
+    let light = (w.min(h) / 8.0).max(1.0);
+    let (cx, cy) = (w / 2.0, h / 2.0);
+    let radius = w.min(h) / 2.0;
+    // Which way the arms leave the centre
+    let sx = if arms[RIGHT] != Weight::None {
+        1.0
+    } else {
+        -1.0
+    };
+    let sy = if arms[DOWN] != Weight::None {
+        1.0
+    } else {
+        -1.0
+    };
+
+    let horizontal_end = if sx > 0.0 { w } else { 0.0 };
+    let from = cx + sx * radius;
+    let (left, right) = (from.min(horizontal_end), from.max(horizontal_end));
+    // The circle reaches the edge along the shorter side, leaving no stroke
+    if right > left {
+        rects.push(Rect::new(
+            x + left,
+            y + cy - light / 2.0,
+            right - left,
+            light,
+        ));
+    }
+    let vertical_end = if sy > 0.0 { h } else { 0.0 };
+    let from = cy + sy * radius;
+    let (top, bottom) = (from.min(vertical_end), from.max(vertical_end));
+    if bottom > top {
+        rects.push(Rect::new(
+            x + cx - light / 2.0,
+            y + top,
+            light,
+            bottom - top,
+        ));
+    }
+
+    // Enough squares that neighbours overlap by half a stroke
+    let steps = (radius * std::f32::consts::FRAC_PI_2 / (light / 2.0))
+        .ceil()
+        .max(1.0) as usize;
+    let (centre_x, centre_y) = (cx + sx * radius, cy + sy * radius);
+    for step in 0..=steps {
+        let angle = step as f32 / steps as f32 * std::f32::consts::FRAC_PI_2;
+        let (px, py) = (
+            centre_x - sx * radius * angle.cos(),
+            centre_y - sy * radius * angle.sin(),
+        );
+        rects.push(Rect::new(
+            x + px - light / 2.0,
+            y + py - light / 2.0,
+            light,
+            light,
+        ));
+    }
+}
+
+type Span = (f32, f32);
+
+/// Stretch the ends of a line that stop inside the cell by half a stroke, so
+/// they cover the perpendicular line they join instead of meeting its centre
+fn overlap_corner((start, end): Span, length: f32, stroke: f32) -> Span {
+    let start = if start > 0.0 {
+        start - stroke / 2.0
+    } else {
+        start
+    };
+    let end = if end < length {
+        end + stroke / 2.0
+    } else {
+        end
+    };
+    (start, end)
+}
+
+/// Spans of the two parallel lines of a double stroke along one axis.
+///
+/// `arms` is `[before, after, first_side, second_side]`: the arms along the
+/// axis, then the perpendicular arms leaving from the first and second line's
+/// side. A line stops on the inner corner when a perpendicular arm leaves from
+/// its own side and on the outer corner otherwise, and is broken where such an
+/// arm crosses it.
+fn double_lines(arms: [bool; 4], centre: f32, length: f32, gap: f32) -> (Vec<Span>, Vec<Span>) {
+    let [before, after, first_side, second_side] = arms;
+    let line = |own_side: bool| -> Vec<Span> {
+        match (before, after) {
+            (true, true) if own_side => vec![(0.0, centre - gap), (centre + gap, length)],
+            (true, true) => vec![(0.0, length)],
+            (true, false) if own_side => vec![(0.0, centre - gap)],
+            (true, false) => vec![(0.0, centre + gap)],
+            (false, true) if own_side => vec![(centre + gap, length)],
+            (false, true) => vec![(centre - gap, length)],
+            (false, false) => Vec::new(),
+        }
+    };
+    (line(first_side), line(second_side))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rects(ch: char) -> Vec<Rect> {
+        let mut rects = Vec::new();
+        assert!(cell_rects(ch, 0.0, 0.0, 8.0, 16.0, &mut rects));
+        rects
+    }
+
+    #[test]
+    fn test_blocks() {
+        assert_eq!(rects('█'), vec![Rect::new(0.0, 0.0, 8.0, 16.0)]);
+        assert_eq!(rects('▄'), vec![Rect::new(0.0, 8.0, 8.0, 8.0)]);
+        assert_eq!(rects('▐'), vec![Rect::new(4.0, 0.0, 4.0, 16.0)]);
+        assert_eq!(rects('▏'), vec![Rect::new(0.0, 0.0, 1.0, 16.0)]);
+        assert_eq!(rects('▚').len(), 2);
+        assert!(rects(' ').is_empty());
+        assert_eq!(rects('▒')[0].opacity, 0.5);
+    }
+
+    #[test]
+    fn test_double_lines() {
+        // Straight lines are two full-length strokes
+        let horizontal = rects('═');
+        assert_eq!(horizontal.len(), 2);
+        assert!(horizontal.iter().all(|r| r.x == 0.0 && r.width == 8.0));
+        let vertical = rects('║');
+        assert!(vertical.iter().all(|r| r.y == 0.0 && r.height == 16.0));
+
+        // A corner has an outer and an inner stroke in each direction
+        let corner = rects('╔');
+        assert_eq!(corner.len(), 4);
+        assert!(corner
+            .iter()
+            .all(|r| r.x + r.width == 8.0 || r.y + r.height == 16.0));
+
+        // A crossing breaks every line
+        assert_eq!(rects('╬').len(), 8);
+    }
+
+    #[test]
+    fn test_rounded_corner() {
+        let corner = rects('╭');
+        // The corner reaches the right and bottom edges only; in a cell this
+        // narrow the arc itself meets the right edge
+        assert!(corner.iter().any(|r| r.x + r.width >= 8.0));
+        assert!(corner.iter().any(|r| r.y + r.height == 16.0));
+        assert!(corner.iter().all(|r| r.x >= 3.5 && r.y >= 3.5));
+        // The arc leaves the corner of the cell empty, unlike a square corner
+        assert!(corner
+            .iter()
+            .all(|r| !(r.x < 5.0 && r.y < 5.0 && r.x + r.width > 4.5 && r.y + r.height > 8.5)));
+        assert!(corner
+            .iter()
+            .any(|r| r.x > 4.0 && r.x < 8.0 && r.y > 4.0 && r.y < 8.0));
+        assert!(corner.iter().all(|r| r.width > 0.0 && r.height > 0.0));
+
+        // In a square cell the circle meets both edges, so there are no
+        // straight strokes left to draw
+        for ch in ['╭', '╮', '╰', '╯'] {
+            let mut square = Vec::new();
+            assert!(cell_rects(ch, 0.0, 0.0, 8.0, 8.0, &mut square));
+            assert!(!square.is_empty());
+            assert!(square.iter().all(|r| r.width > 0.0 && r.height > 0.0));
+        }
+    }
+
+    #[test]
+    fn test_text_has_no_geometry() {
+        let mut rects = Vec::new();
+        assert!(!cell_rects('A', 0.0, 0.0, 8.0, 16.0, &mut rects));
+        assert!(rects.is_empty());
+    }
+}
@@ -0,0 +1,133 @@
+use crate::color::Color;
+use crate::output::CellKind;
+
+/// Monospace fonts known to draw block elements and box-drawing characters
+/// edge to edge, so the art has no gaps between rows or cells
+pub const FONT_STACK: &str = "'DejaVu Sans Mono', 'Cascadia Mono', Menlo, Consolas, 'Liberation Mono', 'Noto Sans Mono', monospace";
+
+#[derive(Debug, Clone, Default)]
+pub struct HtmlOptions {
+    /// Wrap the `<pre>` block in a complete HTML document
+    pub full_page: bool,
+    /// Color of the letter bodies (inherits the page color when unset)
+    pub foreground: Option<Color>,
+    /// Color of the drop shadow (same as the letters when unset)
+    pub shadow: Option<Color>,
+    /// Background color (transparent when unset)
+    pub background: Option<Color>,
+}
+
+/// Convert rendered art into a self-contained HTML `<pre>` block or page.
+///
+/// Runs of letter and shadow cells are wrapped in `blocklet-fill` and
+/// `blocklet-shadow` spans, with any configured colors applied inline so the
+/// snippet can be pasted anywhere without a stylesheet.
+pub fn to_html(art: &str, options: &HtmlOptions) -> String {
+    let mut pre_style = format!(
+        "font-family: {}; line-height: 1; letter-spacing: 0; padding: 0.5em;",
+        FONT_STACK
+    );
+    if let Some(color) = options.foreground {
+        pre_style.push_str(&format!(" color: {};", color));
+    }
+    if let Some(color) = options.background {
+        pre_style.push_str(&format!(" background: {};", color));
+    }
+
+    let mut html = String::new();
+    if options.full_page {
+        html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>blocklet</title>\n</head>\n<body>\n");
+    }
+
+    html.push_str(&format!("<pre class=\"blocklet\" style=\"{}\">", pre_style));
+    for (i, line) in art.lines().enumerate() {
+        if i > 0 {
+            html.push('\n');
+        }
+        push_line(&mut html, line, options);
+    }
+    html.push_str("</pre>\n");
+
+    if options.full_page {
+        html.push_str("</body>\n</html>\n");
+    }
+    html
+}
+
+/// Append one row, grouping consecutive cells of the same kind into a span
+fn push_line(html: &mut String, line: &str, options: &HtmlOptions) {
+    let mut run_kind = CellKind::Empty;
+    for ch in line.chars() {
+        let kind = CellKind::of(ch);
+        if kind != run_kind {
+            if run_kind != CellKind::Empty {
+                html.push_str("</span>");
+            }
+            match kind {
+                CellKind::Fill => html.push_str("<span class=\"blocklet-fill\">"),
+                CellKind::Shadow => match options.shadow {
+                    Some(color) => html.push_str(&format!(
+                        "<span class=\"blocklet-shadow\" style=\"color: {};\">",
+                        color
+                    )),
+                    None => html.push_str("<span class=\"blocklet-shadow\">"),
+                },
+                CellKind::Empty => {}
+            }
+            run_kind = kind;
+        }
+        push_escaped(html, ch);
+    }
+    if run_kind != CellKind::Empty {
+        html.push_str("</span>");
+    }
+}
+
+fn push_escaped(html: &mut String, ch: char) {
+    match ch {
+        '&' => html.push_str("&amp;"),
+        '<' => html.push_str("&lt;"),
+        '>' => html.push_str("&gt;"),
+        '"' => html.push_str("&quot;"),
+        _ => html.push(ch),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::render_text;
+
+    #[test]
+    fn test_html_pre_block() {
+        let art = render_text("HI", "standard_shadow", 0, 7).unwrap();
+        let html = to_html(&art, &HtmlOptions::default());
+        assert!(html.starts_with("<pre class=\"blocklet\""));
+        assert!(html.contains(
+            "<span class=\"blocklet-fill\">██</span><span class=\"blocklet-shadow\">╗</span>"
+        ));
+        assert!(!html.contains("<html>"));
+        assert_eq!(html.matches('\n').count(), 7);
+    }
+
+    #[test]
+    fn test_html_colors_and_page() {
+        let options = HtmlOptions {
+            full_page: true,
+            foreground: Some(Color::rgb(255, 0, 0)),
+            shadow: Some(Color::rgb(0, 0, 255)),
+            background: Some(Color::BLACK),
+        };
+        let html = to_html("█╗", &options);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("color: #ff0000;"));
+        assert!(html.contains("background: #000000;"));
+        assert!(html.contains("<span class=\"blocklet-shadow\" style=\"color: #0000ff;\">╗</span>"));
+    }
+
+    #[test]
+    fn test_html_escapes_markup() {
+        let html = to_html("<&>", &HtmlOptions::default());
+        assert!(html.contains("&lt;&amp;&gt;"));
+    }
+}
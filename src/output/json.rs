@@ -0,0 +1,58 @@
+use crate::renderer::RenderedText;
+use serde::Serialize;
+
+/// Version of the JSON document layout, bumped on incompatible changes
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct Document<'a> {
+    schema_version: u32,
+    #[serde(flatten)]
+    rendered: &'a RenderedText,
+}
+
+/// Serialise rendered text and its layout as a pretty-printed JSON document.
+///
+/// The document holds `schema_version`, `font`, `width`, `height`, `rows`,
+/// `lines` (paragraph, source text, first row, size and whether the line was
+/// broken by wrapping) and `substitutions`.
+pub fn to_json(rendered: &RenderedText) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(&Document {
+        schema_version: SCHEMA_VERSION,
+        rendered,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::{RenderOptions, Renderer};
+    use serde_json::Value;
+
+    #[test]
+    fn test_json_schema() {
+        let options = RenderOptions {
+            font_name: "standard".to_string(),
+            max_width: 30,
+            height: 7,
+            spacing: 1,
+            ..RenderOptions::default()
+        };
+        let rendered = Renderer::new(options)
+            .unwrap()
+            .render_grid(&["HI THERE", "ñ"])
+            .unwrap();
+        let json: Value = serde_json::from_str(&to_json(&rendered).unwrap()).unwrap();
+
+        assert_eq!(json["schema_version"], 1);
+        assert_eq!(json["font"], "standard_shadow");
+        assert_eq!(json["height"], rendered.rows.len());
+        assert_eq!(json["rows"].as_array().unwrap().len(), rendered.rows.len());
+        assert_eq!(json["lines"][0]["text"], "HI");
+        assert_eq!(json["lines"][0]["wrapped"], true);
+        assert_eq!(json["lines"][1]["text"], "THERE");
+        assert_eq!(json["lines"][2]["paragraph"], 1);
+        assert_eq!(json["substitutions"][0]["character"], "ñ");
+        assert_eq!(json["substitutions"][0]["replacement"], "?");
+    }
+}
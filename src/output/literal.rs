@@ -0,0 +1,336 @@
+use std::fmt;
+use std::fmt::Write;
+use std::str::FromStr;
+
+/// Programming languages rendered art can be exported to as a string literal
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Language {
+    Rust,
+    C,
+    Python,
+    Go,
+    Shell,
+    JavaScript,
+}
+
+impl Language {
+    /// Names accepted by `--format`
+    pub const NAMES: &'static [&'static str] = &["rust", "c", "python", "go", "shell", "js"];
+}
+
+impl FromStr for Language {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rust" => Ok(Language::Rust),
+            "c" => Ok(Language::C),
+            "python" => Ok(Language::Python),
+            "go" => Ok(Language::Go),
+            "shell" => Ok(Language::Shell),
+            "js" => Ok(Language::JavaScript),
+            _ => Err(format!("Unknown language '{}'", s)),
+        }
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Language::Rust => "rust",
+            Language::C => "c",
+            Language::Python => "python",
+            Language::Go => "go",
+            Language::Shell => "shell",
+            Language::JavaScript => "js",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LiteralOptions {
+    /// Name of the constant or variable holding the art
+    pub name: String,
+    /// Write every non-ASCII character as an escape sequence, for source
+    /// files that must stay ASCII-only
+    pub escape_unicode: bool,
+}
+
+impl Default for LiteralOptions {
+    fn default() -> Self {
+        Self {
+            name: "BANNER".to_string(),
+            escape_unicode: false,
+        }
+    }
+}
+
+/// Check that `name` can name the constant in every supported language: an
+/// ASCII letter or underscore followed by letters, digits and underscores
+pub fn parse_name(name: &str) -> Result<String, String> {
+    let mut chars = name.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|ch| ch.is_ascii_alphabetic() || ch == '_')
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_');
+    if valid {
+        Ok(name.to_string())
+    } else {
+        Err(format!(
+            "'{}' is not a valid name: use letters, digits and underscores, not starting with a digit",
+            name
+        ))
+    }
+}
+
+/// Export rendered art as a string literal declaration in `language`.
+///
+/// Languages with raw strings (Rust, Go, JavaScript, shell) use them so the
+/// art stays readable in the source; otherwise, or when `escape_unicode` is
+/// set, each row becomes an escaped literal ending in `\n` and the rows are
+/// concatenated. C always uses universal character names, since the source
+/// and execution character sets of a C compiler may not be UTF-8.
+pub fn to_literal(art: &str, language: Language, options: &LiteralOptions) -> String {
+    let name = &options.name;
+    let escape = options.escape_unicode;
+    let count = art.lines().count();
+    let rows: Vec<String> = art
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let mut row = escape_str(line, language, escape);
+            if i + 1 < count {
+                row.push_str("\\n");
+            }
+            row
+        })
+        .collect();
+
+    let mut out = String::new();
+    match language {
+        Language::Rust if !escape => {
+            let hashes = "#".repeat(raw_string_hashes(art));
+            let _ = write!(
+                out,
+                "pub const {}: &str = r{h}\"{}\"{h};",
+                name,
+                art,
+                h = hashes
+            );
+        }
+        Language::Rust => {
+            let _ = writeln!(out, "pub const {}: &str = concat!(", name);
+            for row in &rows {
+                let _ = writeln!(out, "    \"{}\",", row);
+            }
+            out.push_str(");");
+        }
+        Language::C => {
+            let _ = write!(out, "static const char {}[] =", name);
+            for row in &rows {
+                let _ = write!(out, "\n    \"{}\"", row);
+            }
+            if rows.is_empty() {
+                out.push_str(" \"\"");
+            }
+            out.push(';');
+        }
+        Language::Python => {
+            let _ = writeln!(out, "{} = (", name);
+            for row in &rows {
+                let _ = writeln!(out, "    \"{}\"", row);
+            }
+            if rows.is_empty() {
+                out.push_str("    \"\"\n");
+            }
+            out.push(')');
+        }
+        Language::Go if !escape && !art.contains('`') => {
+            let _ = write!(out, "const {} = `{}`", name, art);
+        }
+        Language::Go => {
+            let _ = write!(out, "const {} = \"\"", name);
+            for row in &rows {
+                let _ = write!(out, " +\n\t\"{}\"", row);
+            }
+        }
+        Language::JavaScript if !escape => {
+            let body = art
+                .replace('\\', "\\\\")
+                .replace('`', "\\`")
+                .replace("${", "\\${");
+            let _ = write!(out, "const {} = `{}`;", name, body);
+        }
+        Language::JavaScript => {
+            let _ = write!(out, "const {} =", name);
+            let last = rows.len().saturating_sub(1);
+            for (i, row) in rows.iter().enumerate() {
+                let _ = write!(out, "\n  \"{}\"{}", row, if i < last { " +" } else { "" });
+            }
+            if rows.is_empty() {
+                out.push_str(" \"\"");
+            }
+            out.push(';');
+        }
+        Language::Shell if !escape => {
+            let _ = write!(out, "{}='{}'", name, art.replace('\'', "'\\''"));
+        }
+        Language::Shell => {
+            let _ = write!(out, "{}=$'{}'", name, rows.concat());
+        }
+    }
+    out.push('\n');
+    out
+}
+
+/// The number of `#` needed so a Rust raw string cannot be closed early
+fn raw_string_hashes(text: &str) -> usize {
+    let mut longest = 0;
+    for (i, _) in text.match_indices('"') {
+        let run = text[i + 1..].chars().take_while(|&c| c == '#').count();
+        longest = longest.max(run + 1);
+    }
+    longest.max(1)
+}
+
+/// Escape text for the inside of a quoted string literal in `language`
+fn escape_str(text: &str, language: Language, escape_unicode: bool) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '\\' => out.push_str("\\\\"),
+            '"' if language != Language::Shell => out.push_str("\\\""),
+            '\'' if language == Language::Shell => out.push_str("\\'"),
+            '\t' => out.push_str("\\t"),
+            // C reads every hex digit after `\x` into the escape, so it
+            // gets the fixed three digits of an octal escape instead
+            _ if ch.is_ascii_control() && language == Language::C => {
+                let _ = write!(out, "\\{:03o}", ch as u32);
+            }
+            _ if ch.is_ascii_control() => {
+                let _ = write!(out, "\\x{:02x}", ch as u32);
+            }
+            _ if !ch.is_ascii() && (escape_unicode || language == Language::C) => {
+                push_unicode_escape(&mut out, ch, language);
+            }
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+fn push_unicode_escape(out: &mut String, ch: char, language: Language) {
+    let code = ch as u32;
+    let _ = match language {
+        Language::Rust => write!(out, "\\u{{{:x}}}", code),
+        Language::JavaScript if code > 0xFFFF => write!(out, "\\u{{{:x}}}", code),
+        _ if code > 0xFFFF => write!(out, "\\U{:08x}", code),
+        _ => write!(out, "\\u{:04x}", code),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn literal(art: &str, language: Language, escape_unicode: bool) -> String {
+        to_literal(
+            art,
+            language,
+            &LiteralOptions {
+                escape_unicode,
+                ..LiteralOptions::default()
+            },
+        )
+    }
+
+    #[test]
+    fn test_rust() {
+        assert_eq!(
+            literal("█╗\n ║", Language::Rust, false),
+            "pub const BANNER: &str = r#\"█╗\n ║\"#;\n"
+        );
+        assert_eq!(
+            literal("a\"#b", Language::Rust, false),
+            "pub const BANNER: &str = r##\"a\"#b\"##;\n"
+        );
+        assert_eq!(
+            literal("█\n╗", Language::Rust, true),
+            "pub const BANNER: &str = concat!(\n    \"\\u{2588}\\n\",\n    \"\\u{2557}\",\n);\n"
+        );
+    }
+
+    #[test]
+    fn test_c_uses_universal_character_names() {
+        assert_eq!(
+            literal("█\n\"", Language::C, false),
+            "static const char BANNER[] =\n    \"\\u2588\\n\"\n    \"\\\"\";\n"
+        );
+        // A hex escape would swallow the `a` that follows it
+        assert_eq!(
+            literal("\x01a", Language::C, false),
+            "static const char BANNER[] =\n    \"\\001a\";\n"
+        );
+    }
+
+    #[test]
+    fn test_empty_art() {
+        assert_eq!(
+            literal("", Language::Python, false),
+            "BANNER = (\n    \"\"\n)\n"
+        );
+        assert_eq!(
+            literal("", Language::C, false),
+            "static const char BANNER[] = \"\";\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_name() {
+        assert_eq!(parse_name("_LOGO2").unwrap(), "_LOGO2");
+        for name in ["", "2LOGO", "MY-LOGO", "a b", "x;y", "É"] {
+            assert!(parse_name(name).is_err(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_python_and_go() {
+        assert_eq!(
+            literal("█\n█", Language::Python, false),
+            "BANNER = (\n    \"█\\n\"\n    \"█\"\n)\n"
+        );
+        assert_eq!(
+            literal("█\n█", Language::Go, false),
+            "const BANNER = `█\n█`\n"
+        );
+        assert_eq!(
+            literal("a`b", Language::Go, false),
+            "const BANNER = \"\" +\n\t\"a`b\"\n"
+        );
+        assert_eq!(
+            literal("█", Language::Python, true),
+            "BANNER = (\n    \"\\u2588\"\n)\n"
+        );
+    }
+
+    #[test]
+    fn test_js_and_shell() {
+        assert_eq!(
+            literal("`${x}`", Language::JavaScript, false),
+            "const BANNER = `\\`\\${x}\\``;\n"
+        );
+        assert_eq!(
+            literal("█\n█", Language::JavaScript, true),
+            "const BANNER =\n  \"\\u2588\\n\" +\n  \"\\u2588\";\n"
+        );
+        assert_eq!(
+            literal("it's", Language::Shell, false),
+            "BANNER='it'\\''s'\n"
+        );
+        assert_eq!(
+            literal("█\n'", Language::Shell, true),
+            "BANNER=$'\\u2588\\n\\''\n"
+        );
+    }
+}
@@ -0,0 +1,205 @@
+//! Output backends that turn rendered block art into other formats.
+//!
+//! Every backend takes the plain text produced by the renderer and decides how
+//! to draw each cell, so the same banner can be emitted for a terminal, a web
+//! page or an image.
+
+pub mod comment;
+pub mod geometry;
+pub mod html;
+#[cfg(feature = "serde")]
+pub mod json;
+pub mod literal;
+pub mod png;
+pub mod svg;
+
+use crate::color::Color;
+use literal::Language;
+use std::fmt;
+use std::str::FromStr;
+
+/// The output formats supported by `--format`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Html,
+    Svg,
+    Png,
+    Json,
+    /// A string literal in a programming language
+    Literal(Language),
+}
+
+impl OutputFormat {
+    /// Names accepted on the command line
+    pub const NAMES: &'static [&'static str] = &[
+        "text", "html", "svg", "png", "json", "rust", "c", "python", "go", "shell", "js",
+    ];
+
+    /// Whether the format is binary and should not be written to a terminal
+    pub fn is_binary(self) -> bool {
+        matches!(self, OutputFormat::Png)
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "html" => Ok(OutputFormat::Html),
+            "svg" => Ok(OutputFormat::Svg),
+            "png" => Ok(OutputFormat::Png),
+            "json" => Ok(OutputFormat::Json),
+            _ => s
+                .parse::<Language>()
+                .map(OutputFormat::Literal)
+                .map_err(|_| format!("Unknown output format '{}'", s)),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            OutputFormat::Text => "text",
+            OutputFormat::Html => "html",
+            OutputFormat::Svg => "svg",
+            OutputFormat::Png => "png",
+            OutputFormat::Json => "json",
+            OutputFormat::Literal(language) => return language.fmt(f),
+        };
+        f.write_str(name)
+    }
+}
+
+/// Pixel size of one character cell in the graphical backends
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CellSize {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Default for CellSize {
+    /// Terminal cells are roughly twice as tall as they are wide
+    fn default() -> Self {
+        Self {
+            width: 8,
+            height: 16,
+        }
+    }
+}
+
+impl FromStr for CellSize {
+    type Err = String;
+
+    /// Parse `WIDTHxHEIGHT` (e.g. `8x16`), or a single number for square cells
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "Invalid cell size '{}' (expected WIDTHxHEIGHT, e.g. 8x16)",
+                s
+            )
+        };
+        let parse = |v: &str| {
+            v.trim()
+                .parse::<u32>()
+                .ok()
+                .filter(|&n| n > 0)
+                .ok_or_else(invalid)
+        };
+        match s.split_once(['x', 'X']) {
+            Some((width, height)) => Ok(Self {
+                width: parse(width)?,
+                height: parse(height)?,
+            }),
+            None => {
+                let size = parse(s)?;
+                Ok(Self {
+                    width: size,
+                    height: size,
+                })
+            }
+        }
+    }
+}
+
+/// Options shared by the graphical backends, SVG and PNG
+#[derive(Debug, Clone, Default)]
+pub struct ImageOptions {
+    /// Size of one character cell in pixels
+    pub cell_size: CellSize,
+    /// Color of the letter bodies (black when unset)
+    pub foreground: Option<Color>,
+    /// Color of the drop shadow (same as the letters when unset)
+    pub shadow: Option<Color>,
+    /// Background color (transparent when unset)
+    pub background: Option<Color>,
+}
+
+/// What a single cell of rendered art draws
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CellKind {
+    /// Nothing is drawn
+    Empty,
+    /// Part of the letter body (`█` and the other block elements)
+    Fill,
+    /// Part of the drop shadow (box-drawing characters)
+    Shadow,
+}
+
+impl CellKind {
+    pub fn of(ch: char) -> Self {
+        match ch {
+            ' ' => CellKind::Empty,
+            '\u{2500}'..='\u{257F}' => CellKind::Shadow,
+            _ => CellKind::Fill,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_format() {
+        for name in OutputFormat::NAMES {
+            let format: OutputFormat = name.parse().unwrap();
+            assert_eq!(format.to_string(), *name);
+        }
+        assert_eq!(
+            "rust".parse::<OutputFormat>().unwrap(),
+            OutputFormat::Literal(Language::Rust)
+        );
+        assert!("pdf".parse::<OutputFormat>().is_err());
+    }
+
+    #[test]
+    fn test_parse_cell_size() {
+        assert_eq!(
+            "8x16".parse::<CellSize>().unwrap(),
+            CellSize {
+                width: 8,
+                height: 16
+            }
+        );
+        assert_eq!(
+            "12".parse::<CellSize>().unwrap(),
+            CellSize {
+                width: 12,
+                height: 12
+            }
+        );
+        assert!("0x4".parse::<CellSize>().is_err());
+        assert!("8 by 16".parse::<CellSize>().is_err());
+    }
+
+    #[test]
+    fn test_cell_kind() {
+        assert_eq!(CellKind::of(' '), CellKind::Empty);
+        assert_eq!(CellKind::of('█'), CellKind::Fill);
+        assert_eq!(CellKind::of('╗'), CellKind::Shadow);
+    }
+}
@@ -0,0 +1,299 @@
+use crate::color::Color;
+use crate::output::geometry::{cell_rects, Rect};
+use crate::output::{CellKind, CellSize, ImageOptions};
+use std::io::Write;
+use thiserror::Error;
+
+/// The largest width or height a PNG can have
+const MAX_DIMENSION: u64 = i32::MAX as u64;
+
+/// The most pixels an image may have, keeping its RGBA buffer to 256 MiB
+const MAX_PIXELS: u64 = 1 << 26;
+
+#[derive(Debug, Error)]
+pub enum PngError {
+    #[error("the image would be {columns}x{rows} cells of {cell_width}x{cell_height} pixels, too large for a PNG")]
+    TooLarge {
+        columns: usize,
+        rows: usize,
+        cell_width: u32,
+        cell_height: u32,
+    },
+    /// Letters and other characters are drawn as text, which a PNG cannot
+    /// hold, so they would otherwise vanish from the image
+    #[error(
+        "'{0}' has no block shape to draw in a PNG (try --format svg, which draws it as text)"
+    )]
+    NoShape(char),
+    #[error(transparent)]
+    Encoding(#[from] png::EncodingError),
+}
+
+/// An 8-bit RGBA image
+#[derive(Debug, Clone)]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    /// Row-major RGBA pixels, four bytes each
+    pub pixels: Vec<u8>,
+}
+
+impl Image {
+    /// The RGBA value of a pixel
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let i = self.offset(x, y);
+        [
+            self.pixels[i],
+            self.pixels[i + 1],
+            self.pixels[i + 2],
+            self.pixels[i + 3],
+        ]
+    }
+
+    fn offset(&self, x: u32, y: u32) -> usize {
+        (y as usize * self.width as usize + x as usize) * 4
+    }
+
+    /// Blend `color` over a rectangle, snapped to whole pixels so strokes stay crisp
+    fn fill(&mut self, rect: &Rect, color: Color) {
+        let snap = |start: f32, size: f32, limit: u32| {
+            let from = (start.round().max(0.0) as u32).min(limit);
+            let to = ((start + size).round().max(0.0) as u32).min(limit);
+            // Never let a thin stroke vanish
+            let to = if size > 0.0 && to == from {
+                (from + 1).min(limit)
+            } else {
+                to
+            };
+            (from, to)
+        };
+        let (x0, x1) = snap(rect.x, rect.width, self.width);
+        let (y0, y1) = snap(rect.y, rect.height, self.height);
+
+        let alpha = rect.opacity.clamp(0.0, 1.0);
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let i = self.offset(x, y);
+                let dst = &mut self.pixels[i..i + 4];
+                let dst_alpha = dst[3] as f32 / 255.0;
+                let out_alpha = alpha + dst_alpha * (1.0 - alpha);
+                if out_alpha > 0.0 {
+                    for (channel, src) in dst.iter_mut().zip([color.r, color.g, color.b]) {
+                        let blended = (src as f32 * alpha
+                            + *channel as f32 * dst_alpha * (1.0 - alpha))
+                            / out_alpha;
+                        *channel = blended.round() as u8;
+                    }
+                }
+                dst[3] = (out_alpha * 255.0).round() as u8;
+            }
+        }
+    }
+}
+
+/// Rasterise rendered art into an RGBA image.
+///
+/// Cells are drawn geometrically at `cell_size` pixels, like the SVG backend.
+/// Characters without a geometric form, such as the letters of `--ascii`
+/// art, are an error rather than being left out of the image.
+pub fn rasterize(art: &str, options: &ImageOptions) -> Result<Image, PngError> {
+    let CellSize {
+        width: cell_w,
+        height: cell_h,
+    } = options.cell_size;
+    let foreground = options.foreground.unwrap_or(Color::BLACK);
+    let shadow = options.shadow.unwrap_or(foreground);
+
+    let columns = art
+        .lines()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0);
+    let rows = art.lines().count();
+    let too_large = || PngError::TooLarge {
+        columns,
+        rows,
+        cell_width: cell_w,
+        cell_height: cell_h,
+    };
+    let dimension = |cells: usize, size: u32| {
+        (cells as u64)
+            .checked_mul(size as u64)
+            .filter(|&pixels| pixels <= MAX_DIMENSION)
+            .map(|pixels| pixels as u32)
+    };
+    let (width, height) = dimension(columns, cell_w)
+        .zip(dimension(rows, cell_h))
+        .ok_or_else(too_large)?;
+    let pixels = width as u64 * height as u64;
+    if pixels > MAX_PIXELS {
+        return Err(too_large());
+    }
+    let size = pixels as usize * 4;
+
+    let mut image = Image {
+        width,
+        height,
+        pixels: vec![0; size],
+    };
+    if let Some(background) = options.background {
+        for pixel in image.pixels.chunks_exact_mut(4) {
+            pixel.copy_from_slice(&[background.r, background.g, background.b, 255]);
+        }
+    }
+
+    let mut rects = Vec::new();
+    for (row, line) in art.lines().enumerate() {
+        for (col, ch) in line.chars().enumerate() {
+            let color = match CellKind::of(ch) {
+                CellKind::Empty => continue,
+                CellKind::Fill => foreground,
+                CellKind::Shadow => shadow,
+            };
+            rects.clear();
+            let (x, y) = (col as f32 * cell_w as f32, row as f32 * cell_h as f32);
+            if !cell_rects(ch, x, y, cell_w as f32, cell_h as f32, &mut rects) {
+                return Err(PngError::NoShape(ch));
+            }
+            for rect in &rects {
+                image.fill(rect, color);
+            }
+        }
+    }
+    Ok(image)
+}
+
+/// Rasterise rendered art and encode it as a PNG into `writer`
+pub fn write_png<W: Write>(art: &str, options: &ImageOptions, writer: W) -> Result<(), PngError> {
+    let image = rasterize(art, options)?;
+    let mut encoder = png::Encoder::new(writer, image.width.max(1), image.height.max(1));
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    if image.pixels.is_empty() {
+        // PNG cannot be empty, so emit a single transparent pixel
+        writer.write_image_data(&[0; 4])?;
+    } else {
+        writer.write_image_data(&image.pixels)?;
+    }
+    Ok(writer.finish()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::render_text;
+
+    #[test]
+    fn test_rasterize_cells() {
+        let options = ImageOptions {
+            cell_size: CellSize {
+                width: 4,
+                height: 8,
+            },
+            foreground: Some(Color::rgb(255, 0, 0)),
+            shadow: Some(Color::rgb(0, 0, 255)),
+            background: None,
+        };
+        let image = rasterize("█ ║", &options).unwrap();
+        assert_eq!((image.width, image.height), (12, 8));
+        assert_eq!(image.pixel(0, 0), [255, 0, 0, 255]);
+        assert_eq!(image.pixel(3, 7), [255, 0, 0, 255]);
+        // Empty cells stay transparent
+        assert_eq!(image.pixel(5, 4), [0, 0, 0, 0]);
+        // The double stroke draws two vertical lines in the shadow color
+        let column: Vec<u32> = (8..12).filter(|&x| image.pixel(x, 4)[3] == 255).collect();
+        assert_eq!(column.len(), 2);
+        assert_eq!(image.pixel(column[0], 4), [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn test_background_and_shade() {
+        let options = ImageOptions {
+            cell_size: CellSize {
+                width: 2,
+                height: 2,
+            },
+            foreground: Some(Color::BLACK),
+            background: Some(Color::WHITE),
+            ..ImageOptions::default()
+        };
+        let image = rasterize("▒ ", &options).unwrap();
+        assert_eq!(image.pixel(0, 0), [128, 128, 128, 255]);
+        assert_eq!(image.pixel(3, 1), [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn test_rounded_border() {
+        let options = ImageOptions {
+            cell_size: CellSize {
+                width: 8,
+                height: 16,
+            },
+            ..ImageOptions::default()
+        };
+        let image = rasterize("╭─╮\n╰─╯", &options).unwrap();
+        let drawn = |x: u32, y: u32| image.pixel(x, y)[3] == 255;
+        // Every corner cell joins its neighbours, without filling the outer corner
+        for (x, y) in [
+            (5, 8),
+            (18, 8),
+            (4, 12),
+            (20, 12),
+            (4, 20),
+            (19, 21),
+            (5, 23),
+            (18, 23),
+        ] {
+            assert!(drawn(x, y), "({}, {})", x, y);
+        }
+        for (x, y) in [(0, 0), (4, 8), (19, 8), (4, 24), (19, 24)] {
+            assert!(!drawn(x, y), "({}, {})", x, y);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let options = ImageOptions::default();
+        assert!(matches!(
+            rasterize("#+", &options),
+            Err(PngError::NoShape('#'))
+        ));
+        let huge = ImageOptions {
+            cell_size: CellSize {
+                width: u32::MAX,
+                height: u32::MAX,
+            },
+            ..options
+        };
+        assert!(matches!(
+            rasterize(&"█".repeat(3), &huge),
+            Err(PngError::TooLarge { .. })
+        ));
+
+        // Each side fits in a PNG, but the image as a whole is too large
+        let large = ImageOptions {
+            cell_size: CellSize {
+                width: 1024,
+                height: 1024,
+            },
+            ..options
+        };
+        assert!(matches!(
+            rasterize(&"█".repeat(65), &large),
+            Err(PngError::TooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn test_write_png_signature() {
+        let art = render_text("HI", "standard_shadow", 0, 7).unwrap();
+        let mut bytes = Vec::new();
+        write_png(&art, &ImageOptions::default(), &mut bytes).unwrap();
+        assert_eq!(&bytes[..8], b"\x89PNG\r\n\x1a\n");
+
+        let mut empty = Vec::new();
+        write_png("", &ImageOptions::default(), &mut empty).unwrap();
+        assert_eq!(&empty[..8], b"\x89PNG\r\n\x1a\n");
+    }
+}
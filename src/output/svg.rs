@@ -0,0 +1,157 @@
+use crate::color::Color;
+use crate::output::geometry::{cell_rects, merge_runs, Rect};
+use crate::output::{CellKind, CellSize, ImageOptions};
+use std::fmt::Write;
+
+/// Convert rendered art into a standalone SVG document.
+///
+/// Every cell is drawn as rectangles rather than text, so `█` blocks and the
+/// box-drawing shadow strokes look the same whatever fonts the viewer has.
+/// Characters without a geometric form fall back to `<text>` elements.
+pub fn to_svg(art: &str, options: &ImageOptions) -> String {
+    let CellSize {
+        width: cell_w,
+        height: cell_h,
+    } = options.cell_size;
+    let (cell_w, cell_h) = (cell_w as f32, cell_h as f32);
+    let foreground = options.foreground.unwrap_or(Color::BLACK);
+    let shadow = options.shadow.unwrap_or(foreground);
+
+    let mut fill = Vec::new();
+    let mut shade = Vec::new();
+    let mut text = String::new();
+    let mut columns = 0;
+    let mut rows = 0;
+
+    for (row, line) in art.lines().enumerate() {
+        rows = row + 1;
+        let mut width = 0;
+        for (col, ch) in line.chars().enumerate() {
+            width = col + 1;
+            let (x, y) = (col as f32 * cell_w, row as f32 * cell_h);
+            let rects = match CellKind::of(ch) {
+                CellKind::Empty => continue,
+                CellKind::Fill => &mut fill,
+                CellKind::Shadow => &mut shade,
+            };
+            if !cell_rects(ch, x, y, cell_w, cell_h, rects) {
+                let _ = write!(
+                    text,
+                    "<text x=\"{}\" y=\"{}\" font-size=\"{}\">{}</text>",
+                    x,
+                    y + cell_h * 0.8,
+                    cell_h,
+                    escape(ch)
+                );
+            }
+        }
+        columns = columns.max(width);
+    }
+    merge_runs(&mut fill);
+    merge_runs(&mut shade);
+
+    let width = columns as f32 * cell_w;
+    let height = rows as f32 * cell_h;
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" shape-rendering=\"crispEdges\">\n",
+        w = width,
+        h = height
+    );
+    if let Some(background) = options.background {
+        let _ = writeln!(
+            svg,
+            "<rect width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+            width, height, background
+        );
+    }
+    push_group(&mut svg, "blocklet-fill", foreground, &fill);
+    push_group(&mut svg, "blocklet-shadow", shadow, &shade);
+    if !text.is_empty() {
+        let _ = writeln!(
+            svg,
+            "<g fill=\"{}\" font-family=\"monospace\">{}</g>",
+            foreground, text
+        );
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn push_group(svg: &mut String, class: &str, color: Color, rects: &[Rect]) {
+    if rects.is_empty() {
+        return;
+    }
+    let _ = write!(svg, "<g class=\"{}\" fill=\"{}\">", class, color);
+    for rect in rects {
+        let _ = write!(
+            svg,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"",
+            rect.x, rect.y, rect.width, rect.height
+        );
+        if rect.opacity < 1.0 {
+            let _ = write!(svg, " fill-opacity=\"{}\"", rect.opacity);
+        }
+        svg.push_str("/>");
+    }
+    svg.push_str("</g>\n");
+}
+
+fn escape(ch: char) -> String {
+    match ch {
+        '&' => "&amp;".to_string(),
+        '<' => "&lt;".to_string(),
+        '>' => "&gt;".to_string(),
+        _ => ch.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::render_text;
+
+    #[test]
+    fn test_svg_dimensions_and_groups() {
+        let art = render_text("HI", "standard_shadow", 0, 7).unwrap();
+        let columns = art.lines().map(|l| l.chars().count()).max().unwrap();
+        let svg = to_svg(&art, &ImageOptions::default());
+        assert!(svg.starts_with(&format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"112\" viewBox=\"0 0 {w} 112\"",
+            w = columns * 8
+        )));
+        assert!(svg.contains("<g class=\"blocklet-fill\" fill=\"#000000\">"));
+        assert!(svg.contains("<g class=\"blocklet-shadow\" fill=\"#000000\">"));
+        assert!(!svg.contains("<text"));
+        // Transparent unless a background is requested
+        assert!(!svg.contains(&format!("<rect width=\"{}\" height=\"112\"", columns * 8)));
+        assert_eq!(svg.matches("<rect").count(), svg.matches("/>").count());
+    }
+
+    #[test]
+    fn test_svg_rounded_border() {
+        let svg = to_svg("╭─╮\n│█│\n╰─╯", &ImageOptions::default());
+        assert!(!svg.contains("<text"));
+        assert!(svg.contains("<g class=\"blocklet-shadow\""));
+    }
+
+    #[test]
+    fn test_svg_colors_and_cell_size() {
+        let options = ImageOptions {
+            cell_size: CellSize {
+                width: 10,
+                height: 10,
+            },
+            foreground: Some(Color::rgb(255, 0, 0)),
+            shadow: Some(Color::rgb(0, 0, 255)),
+            background: Some(Color::WHITE),
+        };
+        let svg = to_svg("██╗", &options);
+        assert!(svg.contains("width=\"30\" height=\"10\""));
+        assert!(svg.contains("<rect width=\"30\" height=\"10\" fill=\"#ffffff\"/>"));
+        // The two blocks are merged into one rectangle
+        assert!(
+            svg.contains("fill=\"#ff0000\"><rect x=\"0\" y=\"0\" width=\"20\" height=\"10\"/></g>")
+        );
+        assert!(svg.contains("fill=\"#0000ff\">"));
+    }
+}
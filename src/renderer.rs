@@ -1,6 +1,10 @@
-use crate::font::{get_font, FontError};
+use crate::font::{get_font, Font, FontCharacter, FontError, FALLBACK_CHARACTER};
 use anyhow::{Context, Result};
 use std::cmp;
+use std::io::{self, Write};
+use std::ops::Range;
+use std::str::FromStr;
+use unicode_bidi::{bidi_class, BidiClass, BidiInfo, Level};
 
 #[derive(Debug, Clone)]
 pub struct RenderOptions {
@@ -8,6 +12,42 @@ pub struct RenderOptions {
     pub max_width: u32,
     pub height: u32,
     pub spacing: u32,
+    /// Blank rows between lines of text, whether they come from word wrapping
+    /// or separate texts. Negative values overlap the bottom rows of a line
+    /// with the top rows of the next, e.g. `-1` tucks each line into the
+    /// empty descender row of the shadow font.
+    pub line_gap: i32,
+    /// Keep the input's whitespace: runs of spaces are drawn as written, tabs
+    /// are expanded and newlines start a new line. Otherwise all whitespace
+    /// between words collapses to a single space.
+    pub preserve_whitespace: bool,
+    /// Distance between tab stops, in characters, when preserving whitespace
+    pub tab_width: u32,
+    pub direction: Direction,
+}
+
+/// How glyphs follow each other
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// Left to right, wrapping into lines
+    Horizontal,
+    /// Top to bottom, one glyph per line, centred on the widest glyph
+    Vertical,
+}
+
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "horizontal" => Ok(Direction::Horizontal),
+            "vertical" => Ok(Direction::Vertical),
+            _ => Err(format!(
+                "Unknown direction '{}' (expected horizontal or vertical)",
+                s
+            )),
+        }
+    }
 }
 
 impl Default for RenderOptions {
@@ -17,6 +57,10 @@ impl Default for RenderOptions {
             max_width: 0, // No limit
             height: 5,
             spacing: 1,
+            line_gap: 1,
+            preserve_whitespace: false,
+            tab_width: 4,
+            direction: Direction::Horizontal,
         }
     }
 }
@@ -28,181 +72,706 @@ pub fn render_text(text: &str, font_name: &str, max_width: u32, height: u32) ->
         max_width,
         height,
         spacing: 1,
+        ..RenderOptions::default()
     };
-    
+
     render_text_with_options_internal(text, &options)
 }
 
 /// Render text with optional drop-shadow
-pub fn render_text_with_shadow(text: &str, _font_name: &str, max_width: u32, height: u32, enable_shadow: bool) -> Result<String> {
+pub fn render_text_with_shadow(
+    text: &str,
+    _font_name: &str,
+    max_width: u32,
+    height: u32,
+    enable_shadow: bool,
+) -> Result<String> {
     let font_name = if enable_shadow {
         "standard_shadow"
     } else {
         "standard_solid"
     };
-    
+
     let options = RenderOptions {
         font_name: font_name.to_string(),
         max_width,
         height: if enable_shadow { 7 } else { height },
         spacing: 1,
+        ..RenderOptions::default()
     };
-    
+
     render_text_with_options_internal(text, &options)
 }
 
 /// Render text with full options (deprecated - use render_text or render_text_with_shadow instead)
-pub fn render_text_with_options(text: &str, _font_name: &str, max_width: u32, _height: u32, enable_shadow: bool, _lowercase: bool) -> Result<String> {
+pub fn render_text_with_options(
+    text: &str,
+    _font_name: &str,
+    max_width: u32,
+    _height: u32,
+    enable_shadow: bool,
+    _lowercase: bool,
+) -> Result<String> {
     let font_name = if enable_shadow {
         "standard_shadow"
     } else {
         "standard_solid"
     };
-    
+
     let options = RenderOptions {
         font_name: font_name.to_string(),
         max_width,
         height: 7, // Both shadow and solid are now 7 lines
         spacing: 1,
+        ..RenderOptions::default()
     };
-    
+
     render_text_with_options_internal(text, &options)
 }
 
 /// Render text with full options (internal)
 fn render_text_with_options_internal(text: &str, options: &RenderOptions) -> Result<String> {
-    let font = get_font(&options.font_name)
-        .context(format!("Failed to load font '{}'", options.font_name))?;
-    
-    // Handle empty text
-    if text.is_empty() {
-        return Ok(String::new());
-    }
-    
-    // Split text into words for potential word wrapping
-    let words: Vec<&str> = text.split_whitespace().collect();
-    if words.is_empty() {
-        return Ok(String::new());
-    }
-    
-    let mut result_lines: Vec<Vec<String>> = Vec::new();
-    let mut current_line_chars: Vec<char> = Vec::new();
-    let mut current_line_width = 0;
-    
-    for word in words {
-        let word_chars: Vec<char> = word.chars().collect();
-        let word_width = calculate_word_width(&word_chars, font)?;
-        
-        // Check if we need to wrap to a new line
-        if options.max_width > 0 
-            && !current_line_chars.is_empty() 
-            && current_line_width + options.spacing + word_width > options.max_width {
-            // Render current line and start a new one
-            let line_output = render_character_line(&current_line_chars, font, options.height)?;
-            result_lines.push(line_output);
-            current_line_chars.clear();
-            current_line_width = 0;
+    let mut renderer = Renderer::new(options.clone())?;
+    renderer.render(text)
+}
+
+/// Stream rendered text into a writer without building the output in memory
+pub fn render_to<W: Write>(writer: &mut W, text: &str, options: &RenderOptions) -> Result<()> {
+    let mut renderer = Renderer::new(options.clone())?;
+    renderer.render_to(writer, text)
+}
+
+/// Reusable renderer that lays out text and streams rows into any `io::Write`.
+///
+/// The glyph and line buffers are kept between calls, so rendering many
+/// banners with the same renderer performs no per-call allocations once the
+/// buffers have grown to fit the longest text.
+pub struct Renderer<'a> {
+    font: &'a Font,
+    options: RenderOptions,
+    glyphs: Vec<&'a FontCharacter>,
+    /// The character each glyph was laid out for
+    chars: Vec<char>,
+    lines: Vec<LineLayout>,
+}
+
+/// One laid out line: its glyphs, the source text it came from and whether
+/// it was ended by word wrapping rather than the end of the text
+#[derive(Clone, Debug)]
+struct LineLayout {
+    glyphs: Range<usize>,
+    source: Range<usize>,
+    paragraph: usize,
+    /// Columns of space written before the glyphs
+    indent: usize,
+    right_to_left: bool,
+    wrapped: bool,
+}
+
+/// Rendered art together with its layout, for tools that post-process it
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct RenderedText {
+    /// Name of the font used
+    pub font: String,
+    /// Number of columns in the widest row
+    pub width: usize,
+    /// Number of rows
+    pub height: usize,
+    /// The rendered rows, including the blank rows between lines
+    pub rows: Vec<String>,
+    /// Every rendered line of text, in order
+    pub lines: Vec<RenderedLine>,
+    /// Characters the font does not have, drawn with its fallback glyph
+    pub substitutions: Vec<Substitution>,
+}
+
+/// Metadata for one line of rendered text
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct RenderedLine {
+    /// Index of the input text (e.g. the command line argument) this line belongs to
+    pub paragraph: usize,
+    /// The source text drawn on this line
+    pub text: String,
+    /// Index of the first row of this line in `RenderedText::rows`
+    pub row: usize,
+    /// Number of rows this line occupies
+    pub height: usize,
+    /// Number of columns this line occupies
+    pub width: usize,
+    /// Whether the line was broken by word wrapping
+    pub wrapped: bool,
+    /// Whether the line belongs to a right-to-left paragraph (and is aligned right)
+    pub right_to_left: bool,
+}
+
+/// A character that was replaced by the font's fallback glyph
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Substitution {
+    pub character: char,
+    pub replacement: char,
+}
+
+impl Renderer<'static> {
+    /// Create a renderer for one of the built-in fonts named in `options`
+    pub fn new(options: RenderOptions) -> Result<Self> {
+        let font = get_font(&options.font_name)
+            .context(format!("Failed to load font '{}'", options.font_name))?;
+        Ok(Self::with_font(font, options))
+    }
+}
+
+impl<'a> Renderer<'a> {
+    /// Create a renderer for an already loaded font
+    pub fn with_font(font: &'a Font, options: RenderOptions) -> Self {
+        Self {
+            font,
+            options,
+            glyphs: Vec::new(),
+            chars: Vec::new(),
+            lines: Vec::new(),
         }
-        
-        // Add space before word (except for first word in line)
-        if !current_line_chars.is_empty() {
-            current_line_chars.push(' ');
-            current_line_width += font.get_character(' ')?.width;
+    }
+
+    /// The font this renderer draws with
+    pub fn font(&self) -> &'a Font {
+        self.font
+    }
+
+    /// The options this renderer was created with
+    pub fn options(&self) -> &RenderOptions {
+        &self.options
+    }
+
+    /// Render text into a newly allocated `String`
+    pub fn render(&mut self, text: &str) -> Result<String> {
+        let mut buffer = Vec::new();
+        self.render_to(&mut buffer, text)?;
+        Ok(String::from_utf8(buffer).expect("font data is valid UTF-8"))
+    }
+
+    /// Render text row by row into `writer`.
+    ///
+    /// Lines are separated by `line_gap` blank rows and no trailing newline is
+    /// written, matching `render_text`. Layout happens before anything is
+    /// written, so an unsupported character never leaves partial output.
+    pub fn render_to<W: Write>(&mut self, writer: &mut W, text: &str) -> Result<()> {
+        self.render_all_to(writer, &[text])
+    }
+
+    /// Render several texts (such as the command line arguments) one after
+    /// another into `writer`, spaced exactly like wrapped lines
+    pub fn render_all_to<W: Write, S: AsRef<str>>(
+        &mut self,
+        writer: &mut W,
+        texts: &[S],
+    ) -> Result<()> {
+        self.layout(texts)?;
+        self.write_lines(writer)?;
+        Ok(())
+    }
+
+    /// Render several texts one after another, returning the rows along with
+    /// the layout of every line
+    pub fn render_grid<S: AsRef<str>>(&mut self, texts: &[S]) -> Result<RenderedText> {
+        self.layout(texts)?;
+        let mut buffer = Vec::new();
+        self.write_lines(&mut buffer)?;
+        let output = String::from_utf8(buffer).expect("font data is valid UTF-8");
+        let rows: Vec<String> = if output.is_empty() {
+            Vec::new()
+        } else {
+            output.split('\n').map(str::to_string).collect()
+        };
+
+        let render_height = self.render_height();
+        let stride = render_height - self.overlap() + self.options.line_gap.max(0) as usize;
+        let lines = self
+            .lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let first_row = i * stride;
+                let last_row = cmp::min(first_row + render_height, rows.len());
+                RenderedLine {
+                    paragraph: line.paragraph,
+                    text: texts[line.paragraph].as_ref()[line.source.clone()].to_string(),
+                    row: first_row,
+                    height: render_height,
+                    width: rows[first_row..last_row]
+                        .iter()
+                        .map(|r| r.chars().count())
+                        .max()
+                        .unwrap_or(0),
+                    wrapped: line.wrapped,
+                    right_to_left: line.right_to_left,
+                }
+            })
+            .collect();
+
+        let mut substitutions: Vec<Substitution> = Vec::new();
+        for ch in texts
+            .iter()
+            .flat_map(|text| text.as_ref().chars())
+            .filter(|ch| !ch.is_whitespace())
+        {
+            if !self.font.has_character(ch) && !substitutions.iter().any(|s| s.character == ch) {
+                substitutions.push(Substitution {
+                    character: ch,
+                    replacement: FALLBACK_CHARACTER,
+                });
+            }
         }
-        
-        // Add word characters
-        for ch in word_chars {
-            current_line_chars.push(ch);
-            current_line_width += font.get_character(ch).unwrap_or_else(|_| font.get_character('?').unwrap()).width;
+
+        Ok(RenderedText {
+            font: self.font.name.clone(),
+            width: rows.iter().map(|r| r.chars().count()).max().unwrap_or(0),
+            height: rows.len(),
+            rows,
+            lines,
+            substitutions,
+        })
+    }
+
+    fn render_height(&self) -> usize {
+        cmp::min(self.options.height, self.font.height) as usize
+    }
+
+    /// Rows shared by neighbouring lines when `line_gap` is negative
+    fn overlap(&self) -> usize {
+        cmp::min(
+            self.options.line_gap.min(0).unsigned_abs() as usize,
+            self.render_height(),
+        )
+    }
+
+    /// Write the laid out lines row by row, separated by `line_gap`.
+    ///
+    /// Rows shared by two lines are composed in a small buffer, with the
+    /// lower line drawn over the upper one wherever it is not blank; every
+    /// other row is written straight from the glyph data.
+    fn write_lines<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let render_height = self.render_height();
+        let overlap = self.overlap();
+        let gap = self.options.line_gap.max(0);
+        let mut first_row = true;
+        let mut start_row = |writer: &mut W| -> io::Result<()> {
+            if !first_row {
+                writer.write_all(b"\n")?;
+            }
+            first_row = false;
+            Ok(())
+        };
+
+        let mut held: Vec<String> = Vec::new();
+        for (i, line) in self.lines.iter().enumerate() {
+            if i > 0 {
+                for _ in 0..gap {
+                    start_row(writer)?; // Blank row between text lines
+                }
+            }
+            let last = i + 1 == self.lines.len();
+            let glyphs = &self.glyphs[line.glyphs.clone()];
+            let mut next_held = Vec::new();
+            for row in 0..render_height {
+                let hold = !last && row >= render_height - overlap;
+                if row < held.len() || hold {
+                    let mut buffer = Vec::new();
+                    write_spaces(&mut buffer, line.indent)?;
+                    write_glyph_row(&mut buffer, glyphs, row)?;
+                    let mut text = String::from_utf8(buffer).expect("font data is valid UTF-8");
+                    if let Some(below) = held.get(row) {
+                        text = overlay(below, &text);
+                    }
+                    if hold {
+                        next_held.push(text);
+                    } else {
+                        start_row(writer)?;
+                        writer.write_all(text.as_bytes())?;
+                    }
+                } else {
+                    start_row(writer)?;
+                    write_spaces(writer, line.indent)?;
+                    write_glyph_row(writer, glyphs, row)?;
+                }
+            }
+            held = next_held;
+        }
+
+        Ok(())
+    }
+
+    /// Lay out every text in turn, each starting on a new line
+    fn layout<S: AsRef<str>>(&mut self, texts: &[S]) -> Result<(), FontError> {
+        self.glyphs.clear();
+        self.chars.clear();
+        self.lines.clear();
+        for (paragraph, text) in texts.iter().enumerate() {
+            let text = text.as_ref();
+            let first_line = self.lines.len();
+            if self.options.direction == Direction::Vertical {
+                self.layout_vertical(text, paragraph)?;
+            } else {
+                if self.options.preserve_whitespace {
+                    self.layout_preserved(text, paragraph)?;
+                } else {
+                    self.layout_paragraph(text, paragraph)?;
+                }
+                if text.chars().any(is_right_to_left) {
+                    self.reorder_bidi(text, first_line..self.lines.len())?;
+                }
+            }
+        }
+
+        // Stacked glyphs are centred and right-to-left lines aligned right
+        let glyph_width = |line: &LineLayout| {
+            self.glyphs[line.glyphs.clone()]
+                .iter()
+                .map(|g| g.width as usize)
+                .sum::<usize>()
+        };
+        let widest = self.lines.iter().map(glyph_width).max().unwrap_or(0);
+        for i in 0..self.lines.len() {
+            let spare = widest - glyph_width(&self.lines[i]);
+            if self.options.direction == Direction::Vertical {
+                self.lines[i].indent = spare / 2;
+            } else if self.lines[i].right_to_left {
+                self.lines[i].indent = spare;
+            }
+        }
+        Ok(())
+    }
+
+    /// Split text into words and wrap them into lines of glyphs
+    fn layout_paragraph(&mut self, text: &str, paragraph: usize) -> Result<(), FontError> {
+        let font = self.font;
+        let mut line_start = self.glyphs.len();
+        let mut source_start = 0;
+        let mut source_end = 0;
+        let mut current_line_width = 0;
+
+        for word in text.split_whitespace() {
+            let word_width = calculate_word_width(word, font)?;
+            let word_start = word.as_ptr() as usize - text.as_ptr() as usize;
+
+            // Check if we need to wrap to a new line
+            if self.options.max_width > 0
+                && self.glyphs.len() > line_start
+                && current_line_width + self.options.spacing + word_width > self.options.max_width
+            {
+                self.lines.push(LineLayout {
+                    glyphs: line_start..self.glyphs.len(),
+                    source: source_start..source_end,
+                    paragraph,
+                    indent: 0,
+                    right_to_left: false,
+                    wrapped: true,
+                });
+                line_start = self.glyphs.len();
+                current_line_width = 0;
+            }
+            if self.glyphs.len() == line_start {
+                source_start = word_start;
+            }
+            source_end = word_start + word.len();
+
+            // Add space before word (except for first word in line)
+            if self.glyphs.len() > line_start {
+                current_line_width += self.push_glyph(' ')?;
+            }
+
+            // Add word characters
+            for ch in word.chars() {
+                current_line_width += self.push_glyph(ch)?;
+            }
+        }
+
+        // The last line
+        if self.glyphs.len() > line_start {
+            self.lines.push(LineLayout {
+                glyphs: line_start..self.glyphs.len(),
+                source: source_start..source_end,
+                paragraph,
+                indent: 0,
+                right_to_left: false,
+                wrapped: false,
+            });
         }
+
+        Ok(())
     }
-    
-    // Render the last line
-    if !current_line_chars.is_empty() {
-        let line_output = render_character_line(&current_line_chars, font, options.height)?;
-        result_lines.push(line_output);
-    }
-    
-    // Combine all lines
-    let mut final_output = String::new();
-    for (i, line_rows) in result_lines.iter().enumerate() {
-        if i > 0 {
-            final_output.push('\n'); // Add blank line between text lines
+
+    /// Lay out text one glyph per line. Whitespace between words becomes a
+    /// single blank glyph, or one per character when preserving whitespace.
+    fn layout_vertical(&mut self, text: &str, paragraph: usize) -> Result<(), FontError> {
+        let text_start = text.as_ptr() as usize;
+        if self.options.preserve_whitespace {
+            for (i, ch) in text.char_indices().filter(|&(_, ch)| ch != '\r') {
+                let ch = if ch.is_whitespace() { ' ' } else { ch };
+                self.push_glyph_line(ch, i..i + ch.len_utf8(), paragraph)?;
+            }
+            return Ok(());
         }
-        for row in line_rows {
-            final_output.push_str(row);
-            final_output.push('\n');
+
+        let mut previous_end = None;
+        for word in text.split_whitespace() {
+            let word_start = word.as_ptr() as usize - text_start;
+            if let Some(previous_end) = previous_end {
+                self.push_glyph_line(' ', previous_end..word_start, paragraph)?;
+            }
+            previous_end = Some(word_start + word.len());
+            for (i, ch) in word.char_indices() {
+                let start = word_start + i;
+                self.push_glyph_line(ch, start..start + ch.len_utf8(), paragraph)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reorder the glyphs of a paragraph's lines from logical to visual order
+    /// with the Unicode bidirectional algorithm. Lines are broken first, so
+    /// wrapping follows reading order, and brackets in right-to-left runs are
+    /// mirrored.
+    fn reorder_bidi(&mut self, text: &str, lines: Range<usize>) -> Result<(), FontError> {
+        let base = match unicode_bidi::get_base_direction(text) {
+            unicode_bidi::Direction::Rtl => Level::rtl(),
+            _ => Level::ltr(),
+        };
+
+        let mut line_text = String::new();
+        for line in &mut self.lines[lines] {
+            line.right_to_left = base.is_rtl();
+            line_text.clear();
+            line_text.extend(&self.chars[line.glyphs.clone()]);
+            let info = BidiInfo::new(&line_text, Some(base));
+            let Some(para) = info.paragraphs.first() else {
+                continue;
+            };
+            let levels = info.reordered_levels_per_char(para, para.range.clone());
+            let order = BidiInfo::reorder_visual(&levels);
+
+            let logical: Vec<char> = self.chars[line.glyphs.clone()].to_vec();
+            for (visual, &index) in order.iter().enumerate() {
+                let mut ch = logical[index];
+                if levels[index].is_rtl() {
+                    ch = mirrored_bracket(ch);
+                }
+                self.chars[line.glyphs.start + visual] = ch;
+                self.glyphs[line.glyphs.start + visual] = self.font.get_character(ch)?;
+            }
         }
+        Ok(())
+    }
+
+    /// Add the glyph for `ch` as a line of its own
+    fn push_glyph_line(
+        &mut self,
+        ch: char,
+        source: Range<usize>,
+        paragraph: usize,
+    ) -> Result<(), FontError> {
+        self.push_glyph(ch)?;
+        self.lines.push(LineLayout {
+            glyphs: self.glyphs.len() - 1..self.glyphs.len(),
+            source,
+            paragraph,
+            indent: 0,
+            right_to_left: false,
+            wrapped: false,
+        });
+        Ok(())
     }
-    
-    // Remove trailing newline
-    if final_output.ends_with('\n') {
-        final_output.pop();
+
+    /// Add the glyph for `ch` to the current line, returning its width
+    fn push_glyph(&mut self, ch: char) -> Result<u32, FontError> {
+        let glyph = self.font.get_character(ch)?;
+        self.glyphs.push(glyph);
+        self.chars.push(ch);
+        Ok(glyph.width)
+    }
+
+    /// Lay out text keeping its whitespace. Every newline starts a new line,
+    /// even an empty one; word wrapping still breaks between words and drops
+    /// the spaces at the break.
+    fn layout_preserved(&mut self, text: &str, paragraph: usize) -> Result<(), FontError> {
+        let font = self.font;
+        let space = font.get_character(' ')?;
+        let tab_width = cmp::max(self.options.tab_width, 1) as usize;
+
+        let mut offset = 0;
+        for line in text.split('\n') {
+            let line_start_offset = offset;
+            offset += line.len() + 1;
+            let line = line.strip_suffix('\r').unwrap_or(line);
+
+            let mut line_start = self.glyphs.len();
+            let mut source_start = line_start_offset;
+            let mut current_line_width = 0;
+            let mut column = 0; // Source column, for tab stops
+            let mut pending_spaces = 0;
+
+            let mut rest = line;
+            while let Some(ch) = rest.chars().next() {
+                if ch == ' ' || ch == '\t' {
+                    let spaces = match ch {
+                        '\t' => tab_width - column % tab_width,
+                        _ => 1,
+                    };
+                    pending_spaces += spaces;
+                    column += spaces;
+                    rest = &rest[1..];
+                    continue;
+                }
+
+                let word_len = rest.find([' ', '\t']).unwrap_or(rest.len());
+                let word = &rest[..word_len];
+                let word_start = line_start_offset + line.len() - rest.len();
+                let word_width = calculate_word_width(word, font)?;
+                let spaces_width = pending_spaces as u32 * space.width;
+
+                if self.options.max_width > 0
+                    && self.glyphs.len() > line_start
+                    && current_line_width + spaces_width + word_width > self.options.max_width
+                {
+                    self.lines.push(LineLayout {
+                        glyphs: line_start..self.glyphs.len(),
+                        source: source_start..word_start,
+                        paragraph,
+                        indent: 0,
+                        right_to_left: false,
+                        wrapped: true,
+                    });
+                    line_start = self.glyphs.len();
+                    source_start = word_start;
+                    current_line_width = 0;
+                    pending_spaces = 0;
+                }
+
+                for _ in 0..pending_spaces {
+                    current_line_width += self.push_glyph(' ')?;
+                }
+                pending_spaces = 0;
+
+                for ch in word.chars() {
+                    current_line_width += self.push_glyph(ch)?;
+                }
+                column += word.chars().count();
+                rest = &rest[word_len..];
+            }
+
+            // Trailing whitespace is part of the layout too
+            for _ in 0..pending_spaces {
+                self.push_glyph(' ')?;
+            }
+            self.lines.push(LineLayout {
+                glyphs: line_start..self.glyphs.len(),
+                source: source_start..line_start_offset + line.len(),
+                paragraph,
+                indent: 0,
+                right_to_left: false,
+                wrapped: false,
+            });
+        }
+
+        Ok(())
     }
-    
-    Ok(final_output)
 }
 
 /// Calculate the width of a word in characters
-fn calculate_word_width(chars: &[char], font: &crate::font::Font) -> Result<u32, FontError> {
+fn calculate_word_width(word: &str, font: &Font) -> Result<u32, FontError> {
     let mut width = 0;
-    for &ch in chars {
+    for ch in word.chars() {
         let char_info = font.get_character(ch)?;
         width += char_info.width; // No spacing between characters
     }
     Ok(width)
 }
 
-/// Render a line of characters
-fn render_character_line(chars: &[char], font: &crate::font::Font, target_height: u32) -> Result<Vec<String>, FontError> {
-    if chars.is_empty() {
-        return Ok(vec![String::new(); target_height as usize]);
-    }
-    
-    // Get font characters for all input characters
-    let font_chars: Result<Vec<_>, _> = chars
-        .iter()
-        .map(|&ch| font.get_character(ch))
-        .collect();
-    let font_chars = font_chars?;
-    
-    // Use the font's native height or the requested height
-    let render_height = cmp::min(target_height, font.height) as usize;
-    
-    // Create output lines
-    let mut output_lines = vec![String::new(); render_height];
-    
-    // Process each character - no spacing between characters for shadow font
-    for font_char in font_chars.iter() {
-        // Add character data to each line (no spacing between characters)
-        for (line_idx, output_line) in output_lines.iter_mut().enumerate() {
-            if line_idx < font_char.data.len() {
-                output_line.push_str(&font_char.data[line_idx]);
-            } else {
-                // Pad with spaces if character is shorter than render height
-                output_line.push_str(&" ".repeat(font_char.width as usize));
-            }
+/// Write one row of a line of glyphs - no spacing between characters
+fn write_glyph_row<W: Write>(
+    writer: &mut W,
+    glyphs: &[&FontCharacter],
+    row: usize,
+) -> io::Result<()> {
+    for glyph in glyphs {
+        match glyph.data.get(row) {
+            Some(data) => writer.write_all(data.as_bytes())?,
+            // Pad with spaces if character is shorter than render height
+            None => write_spaces(writer, glyph.width as usize)?,
         }
     }
-    
-    Ok(output_lines)
+    Ok(())
 }
 
+/// Whether `ch` starts or belongs to right-to-left text
+fn is_right_to_left(ch: char) -> bool {
+    matches!(
+        bidi_class(ch),
+        BidiClass::R
+            | BidiClass::AL
+            | BidiClass::AN
+            | BidiClass::RLE
+            | BidiClass::RLO
+            | BidiClass::RLI
+    )
+}
+
+/// The mirror image of a bracket, drawn in right-to-left runs
+fn mirrored_bracket(ch: char) -> char {
+    match ch {
+        '(' => ')',
+        ')' => '(',
+        '[' => ']',
+        ']' => '[',
+        '{' => '}',
+        '}' => '{',
+        '<' => '>',
+        '>' => '<',
+        _ => ch,
+    }
+}
+
+/// Draw `above` over `below`, keeping `below` wherever `above` has a space
+fn overlay(below: &str, above: &str) -> String {
+    let mut below = below.chars();
+    let mut above = above.chars();
+    let mut out = String::new();
+    loop {
+        match (below.next(), above.next()) {
+            (None, None) => return out,
+            (b, Some(' ') | None) => out.push(b.unwrap_or(' ')),
+            (_, Some(a)) => out.push(a),
+        }
+    }
+}
+
+/// Write `count` spaces without allocating
+fn write_spaces<W: Write>(writer: &mut W, mut count: usize) -> io::Result<()> {
+    const SPACES: &[u8] = b"                                ";
+    while count > 0 {
+        let n = cmp::min(count, SPACES.len());
+        writer.write_all(&SPACES[..n])?;
+        count -= n;
+    }
+    Ok(())
+}
 
 /// Render a single character (useful for testing)
 pub fn render_character(ch: char, font_name: &str) -> Result<String> {
-    let font = get_font(font_name)
-        .context(format!("Failed to load font '{}'", font_name))?;
-    
+    let font = get_font(font_name).context(format!("Failed to load font '{}'", font_name))?;
+
     let font_char = font.get_character(ch)?;
-    
+
     Ok(font_char.data.join("\n"))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+    use crate::font::FontSource;
+
     #[test]
     fn test_render_single_character() {
         let result = render_character('A', "standard");
@@ -211,7 +780,7 @@ mod tests {
         assert!(output.contains("█"));
         println!("Character 'A':\n{}", output);
     }
-    
+
     #[test]
     fn test_render_simple_word() {
         let result = render_text("HI", "standard", 0, 5);
@@ -220,7 +789,7 @@ mod tests {
         assert!(output.contains("█"));
         println!("Word 'HI':\n{}", output);
     }
-    
+
     #[test]
     fn test_render_with_numbers() {
         let result = render_text("TEST123", "standard", 0, 5);
@@ -229,38 +798,38 @@ mod tests {
         assert!(output.contains("█"));
         println!("Text 'TEST123':\n{}", output);
     }
-    
+
     #[test]
     fn test_word_wrapping() {
         let result = render_text("HELLO WORLD", "standard", 20, 5);
         assert!(result.is_ok());
         let output = result.unwrap();
         println!("Text with wrapping:\n{}", output);
-        
+
         // Should contain multiple sections separated by blank lines if wrapped
         let lines: Vec<&str> = output.lines().collect();
         assert!(lines.len() > 5); // More than just one word's height
     }
-    
+
     #[test]
     fn test_empty_text() {
         let result = render_text("", "standard", 0, 5);
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "");
     }
-    
+
     #[test]
     fn test_invalid_font() {
         let result = render_text("TEST", "nonexistent", 0, 5);
         assert!(result.is_err());
     }
-    
+
     #[test]
     fn test_no_small_font() {
         let result = render_text("AB", "small", 0, 3);
         assert!(result.is_err());
     }
-    
+
     #[test]
     fn test_render_with_shadow() {
         let result = render_text_with_shadow("HI", "standard", 0, 7, true);
@@ -269,12 +838,12 @@ mod tests {
         assert!(output.contains("█")); // Should contain main blocks
         assert!(output.contains("═")); // Should contain shadow blocks (box drawing)
         println!("Text with shadow 'HI':\n{}", output);
-        
+
         // Should have 7 lines for shadow
         let lines: Vec<&str> = output.lines().collect();
         assert_eq!(lines.len(), 7);
     }
-    
+
     #[test]
     fn test_render_without_shadow() {
         let result = render_text_with_shadow("HI", "standard", 0, 7, false);
@@ -283,21 +852,286 @@ mod tests {
         assert!(output.contains("█")); // Should contain main blocks
         assert!(!output.contains("═")); // Should not contain shadow blocks
         println!("Text without shadow 'HI':\n{}", output);
-        
+
         // Should have 7 lines without shadow (solid version is now 7 lines to match shadow)
         let lines: Vec<&str> = output.lines().collect();
         assert_eq!(lines.len(), 7);
     }
-    
+
     #[test]
     fn test_shadow_offset() {
         let result = render_text_with_shadow("A", "standard", 0, 7, true);
         assert!(result.is_ok());
         let output = result.unwrap();
         let lines: Vec<&str> = output.lines().collect();
-        
+
         // The shadow line (6th line) should have box drawing characters
         assert!(lines.len() == 7);
         assert!(lines[5].contains('═') || lines[5].contains('╝'));
     }
+
+    #[test]
+    fn test_render_to_matches_render_text() {
+        let options = RenderOptions {
+            font_name: "standard_shadow".to_string(),
+            max_width: 40,
+            height: 7,
+            spacing: 1,
+            ..RenderOptions::default()
+        };
+        let mut streamed = Vec::new();
+        render_to(&mut streamed, "HELLO STREAMING WORLD", &options).unwrap();
+        let expected = render_text("HELLO STREAMING WORLD", "standard_shadow", 40, 7).unwrap();
+        assert_eq!(String::from_utf8(streamed).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_render_grid_metadata() {
+        let options = RenderOptions {
+            font_name: "standard_shadow".to_string(),
+            max_width: 40,
+            height: 7,
+            spacing: 1,
+            ..RenderOptions::default()
+        };
+        let mut renderer = Renderer::new(options).unwrap();
+        let grid = renderer.render_grid(&["HELLO  WORLD", "Hé"]).unwrap();
+
+        assert_eq!(grid.font, "standard_shadow");
+        assert_eq!(grid.rows.len(), grid.height);
+        assert_eq!(grid.height, 7 + 1 + 7 + 1 + 7);
+        assert_eq!(grid.lines.len(), 3);
+        assert_eq!(grid.lines[0].text, "HELLO");
+        assert!(grid.lines[0].wrapped);
+        assert_eq!(grid.lines[1].text, "WORLD");
+        assert_eq!(grid.lines[1].row, 8);
+        assert!(!grid.lines[1].wrapped);
+        assert_eq!(grid.lines[2].paragraph, 1);
+        assert_eq!(grid.lines[2].row, 16);
+        assert_eq!(
+            grid.substitutions,
+            vec![Substitution {
+                character: 'é',
+                replacement: '?'
+            }]
+        );
+
+        let text = renderer.render("HELLO  WORLD").unwrap();
+        assert_eq!(grid.rows[..15].join("\n"), text);
+    }
+
+    #[test]
+    fn test_line_gap() {
+        let options = |line_gap| RenderOptions {
+            font_name: "standard_shadow".to_string(),
+            max_width: 30,
+            height: 7,
+            line_gap,
+            ..RenderOptions::default()
+        };
+        let wrapped = Renderer::new(options(0))
+            .unwrap()
+            .render("HELLO WORLD")
+            .unwrap();
+        let separate = Renderer::new(options(0))
+            .unwrap()
+            .render_grid(&["HELLO", "WORLD"])
+            .unwrap();
+        assert_eq!(wrapped.lines().count(), 14);
+        assert_eq!(separate.rows.join("\n"), wrapped);
+        assert_eq!(
+            Renderer::new(options(3))
+                .unwrap()
+                .render("HELLO WORLD")
+                .unwrap()
+                .lines()
+                .count(),
+            17
+        );
+
+        // The empty descender row of the upper line is drawn over by the lower one
+        let mut renderer = Renderer::new(options(-1)).unwrap();
+        let grid = renderer.render_grid(&["HELLO", "WORLD"]).unwrap();
+        assert_eq!(grid.height, 13);
+        assert_eq!(grid.lines[1].row, 6);
+        assert_eq!(grid.rows[6], wrapped.lines().nth(7).unwrap());
+        assert_eq!(
+            grid.rows.join("\n"),
+            renderer.render("HELLO WORLD").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_overlapping_rows_merge() {
+        assert_eq!(overlay("╚═╝   ", "  ██ "), "╚═██  ");
+        assert_eq!(overlay("ab", "  cd"), "abcd");
+    }
+
+    #[test]
+    fn test_preserve_whitespace() {
+        let options = RenderOptions {
+            font_name: "standard_shadow".to_string(),
+            height: 7,
+            preserve_whitespace: true,
+            ..RenderOptions::default()
+        };
+        let mut renderer = Renderer::new(options.clone()).unwrap();
+        let spaced = renderer.render("A   B").unwrap();
+        let single = render_text("A B", "standard_shadow", 0, 7).unwrap();
+        let width = |art: &str| art.lines().next().unwrap().chars().count();
+        assert_eq!(width(&spaced), width(&single) + 2 * 3);
+
+        // Tabs stop every four columns, and newlines break lines like separate texts
+        assert_eq!(renderer.render("A\tB").unwrap(), spaced);
+        assert_eq!(
+            renderer.render("AB\r\nCD").unwrap(),
+            renderer.render_grid(&["AB", "CD"]).unwrap().rows.join("\n")
+        );
+
+        let grid = renderer.render_grid(&["A\n\nB "]).unwrap();
+        assert_eq!(grid.lines.len(), 3);
+        assert_eq!(grid.lines[1].width, 0);
+        assert_eq!(grid.lines[2].text, "B ");
+        assert_eq!(
+            grid.lines[2].width,
+            width(&renderer.render("B").unwrap()) + 3
+        );
+    }
+
+    #[test]
+    fn test_preserve_whitespace_wrapping() {
+        let options = RenderOptions {
+            font_name: "standard_shadow".to_string(),
+            max_width: 20,
+            height: 7,
+            preserve_whitespace: true,
+            ..RenderOptions::default()
+        };
+        let grid = Renderer::new(options)
+            .unwrap()
+            .render_grid(&["  AB    CD"])
+            .unwrap();
+        assert_eq!(grid.lines.len(), 2);
+        assert_eq!(grid.lines[0].text, "  AB    ");
+        assert!(grid.lines[0].wrapped);
+        assert_eq!(grid.lines[1].text, "CD");
+        assert_eq!(
+            grid.rows[0],
+            format!(
+                "      {}",
+                render_text("AB", "standard_shadow", 0, 7)
+                    .unwrap()
+                    .lines()
+                    .next()
+                    .unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn test_vertical_direction() {
+        let options = RenderOptions {
+            font_name: "standard_shadow".to_string(),
+            height: 7,
+            line_gap: 0,
+            direction: Direction::Vertical,
+            ..RenderOptions::default()
+        };
+        let grid = Renderer::new(options)
+            .unwrap()
+            .render_grid(&["HI  A"])
+            .unwrap();
+        let texts: Vec<&str> = grid.lines.iter().map(|line| line.text.as_str()).collect();
+        assert_eq!(texts, ["H", "I", "  ", "A"]);
+        assert_eq!(grid.height, 4 * 7);
+
+        // Narrow glyphs are centred on the widest one
+        let h = render_character('H', "standard_shadow").unwrap();
+        let i = render_character('I', "standard_shadow").unwrap();
+        let indent = (h.lines().next().unwrap().chars().count()
+            - i.lines().next().unwrap().chars().count())
+            / 2;
+        assert_eq!(
+            grid.rows[7],
+            format!("{}{}", " ".repeat(indent), i.lines().next().unwrap())
+        );
+        assert_eq!(
+            "vertical".parse::<Direction>().unwrap(),
+            Direction::Vertical
+        );
+    }
+
+    /// A one-row font that draws every character as itself, so the visual
+    /// order of glyphs can be read straight from the output
+    fn identity_font(chars: &str) -> Font {
+        let characters = chars
+            .chars()
+            .map(|ch| {
+                (
+                    ch,
+                    FontCharacter {
+                        width: 1,
+                        height: 1,
+                        data: vec![ch.to_string()],
+                    },
+                )
+            })
+            .collect();
+        Font {
+            name: "identity".to_string(),
+            description: String::new(),
+            height: 1,
+            characters,
+            source: FontSource::BuiltIn,
+        }
+    }
+
+    #[test]
+    fn test_bidi_reordering() {
+        let font = identity_font("אבגד ABC12()?");
+        let options = RenderOptions {
+            height: 1,
+            line_gap: 0,
+            ..RenderOptions::default()
+        };
+        let mut renderer = Renderer::with_font(&font, options.clone());
+
+        assert_eq!(renderer.render("ABC").unwrap(), "ABC");
+        assert_eq!(renderer.render("אבג").unwrap(), "גבא");
+        assert_eq!(renderer.render("A אב(ג) 12").unwrap(), "A 12 (ג)בא");
+
+        // Right-to-left paragraphs keep numbers left to right and align right
+        let grid = renderer.render_grid(&["אב 12", "ABC DEF"]).unwrap();
+        assert_eq!(grid.rows, ["  12 בא", "ABC ???"]);
+        assert!(grid.lines[0].right_to_left);
+        assert!(!grid.lines[1].right_to_left);
+
+        // Lines wrap in reading order before they are reordered
+        let options = RenderOptions {
+            max_width: 5,
+            ..options
+        };
+        let wrapped = Renderer::with_font(&font, options)
+            .render("אב גד בב")
+            .unwrap();
+        assert_eq!(wrapped, "דג בא\n   בב");
+    }
+
+    #[test]
+    fn test_renderer_reuse() {
+        let options = RenderOptions {
+            font_name: "standard".to_string(),
+            height: 7,
+            ..RenderOptions::default()
+        };
+        let mut renderer = Renderer::new(options).unwrap();
+        let first = renderer.render("LONGER TEXT FIRST").unwrap();
+        let second = renderer.render("HI").unwrap();
+        assert_eq!(
+            first,
+            render_text("LONGER TEXT FIRST", "standard", 0, 7).unwrap()
+        );
+        assert_eq!(second, render_text("HI", "standard", 0, 7).unwrap());
+        assert_eq!(renderer.render("   ").unwrap(), "");
+    }
 }
@@ -0,0 +1,707 @@
+//! Geometric transformations of rendered art and fonts that keep block
+//! elements and box-drawing strokes coherent.
+
+use crate::boxdraw::{box_arms, from_arms, restyle, stroke, Weight, DOWN, LEFT, RIGHT, UP};
+use crate::font::{Font, FontCharacter};
+use std::str::FromStr;
+
+/// The side of the letters a font's drop shadow falls on
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShadowDirection {
+    /// Below and to the right, as the built-in fonts are drawn
+    Right,
+    /// Below and to the left, for right-to-left text
+    Left,
+}
+
+impl ShadowDirection {
+    /// Names accepted by `--shadow-direction`
+    pub const NAMES: &'static [&'static str] = &["right", "left"];
+}
+
+impl FromStr for ShadowDirection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "right" => Ok(ShadowDirection::Right),
+            "left" => Ok(ShadowDirection::Left),
+            _ => Err(format!(
+                "Unknown shadow direction '{}' (expected right or left)",
+                s
+            )),
+        }
+    }
+}
+
+/// Clockwise rotations for `--rotate`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rotation {
+    Quarter,
+    Half,
+    ThreeQuarters,
+}
+
+impl Rotation {
+    /// Degrees accepted by `--rotate`
+    pub const NAMES: &'static [&'static str] = &["90", "180", "270"];
+
+    fn quarter_turns(self) -> usize {
+        match self {
+            Rotation::Quarter => 1,
+            Rotation::Half => 2,
+            Rotation::ThreeQuarters => 3,
+        }
+    }
+}
+
+impl FromStr for Rotation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "90" => Ok(Rotation::Quarter),
+            "180" => Ok(Rotation::Half),
+            "270" => Ok(Rotation::ThreeQuarters),
+            _ => Err(format!(
+                "Unsupported rotation '{}' (expected 90, 180 or 270)",
+                s
+            )),
+        }
+    }
+}
+
+/// Enlarge every cell of `art` to `scale_x` columns by `scale_y` rows.
+///
+/// Filled cells are repeated. Half, quadrant and eighth blocks keep their
+/// shape, so `▀` scaled by 2 becomes a full block above an empty one. A
+/// box-drawing character is drawn once in the top-left corner of its
+/// enlarged cell and its right and down arms are extended with `─`, `║` and
+/// the like, so strokes stay continuous and corners are never duplicated.
+pub fn scale(art: &str, scale_x: usize, scale_y: usize) -> String {
+    let rows: Vec<String> = art
+        .lines()
+        .flat_map(|line| scale_row(line, scale_x, scale_y))
+        .collect();
+    rows.join("\n")
+}
+
+/// Derive a font whose glyphs are enlarged by [`scale`], so wrapping and
+/// every output format see the larger cells
+pub fn scale_font(font: &Font, scale_x: usize, scale_y: usize) -> Font {
+    let characters = font
+        .characters
+        .iter()
+        .map(|(ch, glyph)| {
+            let data = glyph
+                .data
+                .iter()
+                .flat_map(|row| scale_row(row, scale_x, scale_y))
+                .collect();
+            let glyph = FontCharacter {
+                width: glyph.width * scale_x as u32,
+                height: glyph.height * scale_y as u32,
+                data,
+            };
+            (*ch, glyph)
+        })
+        .collect();
+
+    Font {
+        name: format!("{}_{}x{}", font.name, scale_x, scale_y),
+        description: format!("{} (scaled {}x{})", font.description, scale_x, scale_y),
+        height: font.height * scale_y as u32,
+        characters,
+        source: font.source.clone(),
+    }
+}
+
+/// Derive a font whose drop shadow falls to the left of the letters.
+///
+/// The shadow strokes are redrawn from each glyph's body as the outline of
+/// the body moved half a cell down and to the left, hidden where it passes
+/// behind the body. The body moves one column right to make room, so glyph
+/// widths are unchanged. Glyphs without a shadow, such as those of the solid
+/// font, are kept as they are.
+pub fn left_shadow_font(font: &Font) -> Font {
+    let characters = font
+        .characters
+        .iter()
+        .map(|(ch, glyph)| {
+            let has_shadow = glyph
+                .data
+                .iter()
+                .any(|row| row.chars().any(|c| box_arms(c).is_some()));
+            let data = if has_shadow {
+                left_shadow(&glyph.data)
+            } else {
+                glyph.data.clone()
+            };
+            (
+                *ch,
+                FontCharacter {
+                    data,
+                    ..glyph.clone()
+                },
+            )
+        })
+        .collect();
+
+    Font {
+        name: format!("{}_left", font.name),
+        description: format!("{} (shadow to the left)", font.description),
+        height: font.height,
+        characters,
+        source: font.source.clone(),
+    }
+}
+
+/// Redraw the drop shadow of a glyph from its body, the cells that are
+/// neither blank nor box drawing, replacing any shadow it already has. A
+/// column is added when the body reaches the right edge so the shadow fits.
+pub fn shadow(rows: &[String]) -> Vec<String> {
+    let mut body: Vec<Vec<Option<char>>> = rows
+        .iter()
+        .map(|row| {
+            row.chars()
+                .map(|c| Some(c).filter(|&c| c != ' ' && box_arms(c).is_none()))
+                .collect()
+        })
+        .collect();
+    let width = body.iter().map(Vec::len).max().unwrap_or(0);
+    let reaches_edge = body
+        .iter()
+        .any(|row| row.len() == width && row.last().is_some_and(Option::is_some));
+    let width = width + reaches_edge as usize;
+    for row in &mut body {
+        row.resize(width, None);
+    }
+    drop_shadow(&body)
+        .into_iter()
+        .map(|row| row.into_iter().collect())
+        .collect()
+}
+
+/// Redraw the shadow of one glyph on the left. The right shadow of the
+/// mirrored body is drawn and the result mirrored back.
+fn left_shadow(rows: &[String]) -> Vec<String> {
+    let width = rows
+        .iter()
+        .map(|row| row.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mirrored_body: Vec<Vec<Option<char>>> = rows
+        .iter()
+        .map(|row| {
+            let mut cells: Vec<Option<char>> = row
+                .chars()
+                .map(|ch| {
+                    Some(mirror_horizontal(ch)).filter(|&c| c != ' ' && box_arms(c).is_none())
+                })
+                .collect();
+            cells.resize(width, None);
+            // Mirroring the body moved one column right leaves the last column free
+            cells.pop();
+            cells.reverse();
+            cells.push(None);
+            cells
+        })
+        .collect();
+
+    drop_shadow(&mirrored_body)
+        .into_iter()
+        .map(|row| row.into_iter().rev().map(mirror_horizontal).collect())
+        .collect()
+}
+
+/// Draw `body` with a double-line shadow below and to the right: the outline
+/// of the body moved half a cell down and right, so it runs through the
+/// centres of the cells it passes, hidden behind the body itself
+fn drop_shadow(body: &[Vec<Option<char>>]) -> Vec<Vec<char>> {
+    let filled = |x: usize, y: usize, dx: usize, dy: usize| -> bool {
+        x >= dx
+            && y >= dy
+            && body
+                .get(y - dy)
+                .and_then(|row| row.get(x - dx))
+                .is_some_and(Option::is_some)
+    };
+    body.iter()
+        .enumerate()
+        .map(|(y, row)| {
+            (0..row.len())
+                .map(|x| {
+                    if let Some(ch) = row[x] {
+                        return ch;
+                    }
+                    // The four cells of the moved body meeting at this cell's centre
+                    let (upper_left, upper_right) = (filled(x, y, 1, 1), filled(x, y, 0, 1));
+                    let (lower_left, lower_right) = (filled(x, y, 1, 0), false);
+                    let edge =
+                        |a: bool, b: bool| if a != b { Weight::Double } else { Weight::None };
+                    let mut arms = [Weight::None; 4];
+                    arms[LEFT] = edge(upper_left, lower_left);
+                    arms[RIGHT] = edge(upper_right, lower_right);
+                    arms[UP] = edge(upper_left, upper_right);
+                    arms[DOWN] = edge(lower_left, lower_right);
+                    if arms.iter().all(|&arm| arm == Weight::Double) {
+                        // Two corners touching diagonally: keep the upper one
+                        arms[LEFT] = Weight::None;
+                        arms[DOWN] = Weight::None;
+                    }
+                    from_arms(arms).unwrap_or(' ')
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Scale one row of art into `scale_y` rows
+fn scale_row(row: &str, scale_x: usize, scale_y: usize) -> Vec<String> {
+    (0..scale_y)
+        .map(|sub_row| {
+            row.chars()
+                .flat_map(|ch| {
+                    (0..scale_x)
+                        .map(move |sub_col| scaled_cell(ch, sub_col, sub_row, scale_x, scale_y))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// The character at (`sub_col`, `sub_row`) of `ch` enlarged to
+/// `scale_x` x `scale_y` cells
+fn scaled_cell(ch: char, sub_col: usize, sub_row: usize, scale_x: usize, scale_y: usize) -> char {
+    if let Some(arms) = box_arms(ch) {
+        return match (sub_col, sub_row) {
+            (0, 0) => ch,
+            (_, 0) => stroke(arms[RIGHT], true),
+            (0, _) => stroke(arms[DOWN], false),
+            _ => ' ',
+        };
+    }
+    if let Some(quadrants) = quadrants(ch) {
+        let horizontal = half(sub_col, scale_x);
+        let vertical = half(sub_row, scale_y);
+        let [upper_left, upper_right, lower_left, lower_right] = quadrants;
+        let [upper_left, upper_right, lower_left, lower_right] = match horizontal {
+            Half::First => [upper_left, upper_left, lower_left, lower_left],
+            Half::Second => [upper_right, upper_right, lower_right, lower_right],
+            Half::Both => quadrants,
+        };
+        let clipped = match vertical {
+            Half::First => [upper_left, upper_right, upper_left, upper_right],
+            Half::Second => [lower_left, lower_right, lower_left, lower_right],
+            Half::Both => [upper_left, upper_right, lower_left, lower_right],
+        };
+        return quadrant_char(clipped);
+    }
+    // Eighth blocks: fill the sub-cells inside the bar, measuring each
+    // sub-cell's distance from the edge the bar grows from
+    match ch {
+        '▁'..='▇' => {
+            let eighths = (ch as u32 - '▁' as u32 + 1) as usize;
+            match bar_filled(eighths * scale_y, scale_y - 1 - sub_row) {
+                0 => ' ',
+                8 => '█',
+                n => char::from_u32('▁' as u32 + n as u32 - 1).unwrap(),
+            }
+        }
+        '▉'..='▏' => {
+            let eighths = ('▐' as u32 - ch as u32) as usize;
+            match bar_filled(eighths * scale_x, sub_col) {
+                0 => ' ',
+                8 => '█',
+                n => char::from_u32('▐' as u32 - n as u32).unwrap(),
+            }
+        }
+        // Only one, four and eight eighths exist along the top and right,
+        // so these bars take the nearest of them
+        '▔' => match bar_filled(scale_y, sub_row) {
+            0 => ' ',
+            1 | 2 => '▔',
+            3..=6 => '▀',
+            _ => '█',
+        },
+        '▕' => match bar_filled(scale_x, scale_x - 1 - sub_col) {
+            0 => ' ',
+            1 | 2 => '▕',
+            3..=6 => '▐',
+            _ => '█',
+        },
+        _ => ch,
+    }
+}
+
+/// How many eighths of a bar `length` eighths long fall in the sub-cell
+/// `index` cells from the edge it starts at
+fn bar_filled(length: usize, index: usize) -> usize {
+    length.saturating_sub(index * 8).min(8)
+}
+
+/// Rotate `art` clockwise as a rectangular canvas.
+///
+/// Characters are turned along with the canvas: box-drawing characters keep
+/// their arms connected, quadrant and half blocks move their filled parts and
+/// eighth blocks become the nearest bar on the rotated side. Terminal cells
+/// are taller than they are wide, so quarter turns look stretched.
+pub fn rotate(art: &str, rotation: Rotation) -> String {
+    let grid = canvas(art);
+    let height = grid.len();
+    let width = grid.first().map_or(0, Vec::len);
+    let turns = rotation.quarter_turns();
+    let cell = |row: usize, col: usize| {
+        let ch = match rotation {
+            Rotation::Quarter => grid[height - 1 - col][row],
+            Rotation::Half => grid[height - 1 - row][width - 1 - col],
+            Rotation::ThreeQuarters => grid[col][width - 1 - row],
+        };
+        (0..turns).fold(ch, |ch, _| quarter_turn(ch))
+    };
+
+    let (rows, cols) = if turns == 2 {
+        (height, width)
+    } else {
+        (width, height)
+    };
+    let rows: Vec<String> = (0..rows)
+        .map(|row| (0..cols).map(|col| cell(row, col)).collect())
+        .collect();
+    rows.join("\n")
+}
+
+/// Mirror `art` left to right, so `╗` becomes `╔` and `▌` becomes `▐`
+pub fn flip_horizontal(art: &str) -> String {
+    let rows: Vec<String> = canvas(art)
+        .into_iter()
+        .map(|row| row.into_iter().rev().map(mirror_horizontal).collect())
+        .collect();
+    rows.join("\n")
+}
+
+/// Mirror `art` top to bottom, so `╗` becomes `╝` and `▀` becomes `▄`
+pub fn flip_vertical(art: &str) -> String {
+    let rows: Vec<String> = canvas(art)
+        .into_iter()
+        .rev()
+        .map(|row| row.into_iter().map(mirror_vertical).collect())
+        .collect();
+    rows.join("\n")
+}
+
+/// The rows of `art` as characters, padded with spaces to the widest row
+fn canvas(art: &str) -> Vec<Vec<char>> {
+    let width = art
+        .lines()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0);
+    art.lines()
+        .map(|line| {
+            let mut row: Vec<char> = line.chars().collect();
+            row.resize(width, ' ');
+            row
+        })
+        .collect()
+}
+
+/// `ch` turned a quarter clockwise
+fn quarter_turn(ch: char) -> char {
+    if let Some(arms) = box_arms(ch) {
+        let mut turned = arms;
+        turned[RIGHT] = arms[UP];
+        turned[DOWN] = arms[RIGHT];
+        turned[LEFT] = arms[DOWN];
+        turned[UP] = arms[LEFT];
+        return restyle(ch, turned).unwrap_or(ch);
+    }
+    if let Some([upper_left, upper_right, lower_left, lower_right]) = quadrants(ch) {
+        return quadrant_char([lower_left, upper_left, lower_right, upper_right]);
+    }
+    if let Some((side, eighths)) = bar(ch) {
+        let side = match side {
+            Side::Bottom => Side::Left,
+            Side::Left => Side::Top,
+            Side::Top => Side::Right,
+            Side::Right => Side::Bottom,
+        };
+        return bar_char(side, eighths);
+    }
+    match ch {
+        '-' => '|',
+        '|' => '-',
+        '/' => '\\',
+        '\\' => '/',
+        _ => ch,
+    }
+}
+
+/// `ch` mirrored left to right
+pub fn mirror_horizontal(ch: char) -> char {
+    if let Some(mut arms) = box_arms(ch) {
+        arms.swap(LEFT, RIGHT);
+        return restyle(ch, arms).unwrap_or(ch);
+    }
+    if let Some([upper_left, upper_right, lower_left, lower_right]) = quadrants(ch) {
+        return quadrant_char([upper_right, upper_left, lower_right, lower_left]);
+    }
+    if let Some((side, eighths)) = bar(ch) {
+        let side = match side {
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+            side => side,
+        };
+        return bar_char(side, eighths);
+    }
+    match ch {
+        '/' => '\\',
+        '\\' => '/',
+        '(' => ')',
+        ')' => '(',
+        '[' => ']',
+        ']' => '[',
+        '{' => '}',
+        '}' => '{',
+        '<' => '>',
+        '>' => '<',
+        _ => ch,
+    }
+}
+
+/// `ch` mirrored top to bottom
+pub fn mirror_vertical(ch: char) -> char {
+    if let Some(mut arms) = box_arms(ch) {
+        arms.swap(UP, DOWN);
+        return restyle(ch, arms).unwrap_or(ch);
+    }
+    if let Some([upper_left, upper_right, lower_left, lower_right]) = quadrants(ch) {
+        return quadrant_char([lower_left, lower_right, upper_left, upper_right]);
+    }
+    if let Some((side, eighths)) = bar(ch) {
+        let side = match side {
+            Side::Top => Side::Bottom,
+            Side::Bottom => Side::Top,
+            side => side,
+        };
+        return bar_char(side, eighths);
+    }
+    match ch {
+        '/' => '\\',
+        '\\' => '/',
+        _ => ch,
+    }
+}
+
+/// The side of the cell an eighth block is attached to
+#[derive(Clone, Copy)]
+enum Side {
+    Top,
+    Right,
+    Bottom,
+    Left,
+}
+
+/// Eighth blocks that are not also quadrant blocks, as their side and size
+fn bar(ch: char) -> Option<(Side, u32)> {
+    match ch {
+        '▁'..='▇' => Some((Side::Bottom, ch as u32 - '▁' as u32 + 1)),
+        '▉'..='▏' => Some((Side::Left, 0x2590 - ch as u32)),
+        '▔' => Some((Side::Top, 1)),
+        '▕' => Some((Side::Right, 1)),
+        _ => None,
+    }
+}
+
+/// The block for a bar of `eighths` on `side`. Unicode only has the full
+/// set of eighths at the bottom and left, so top and right bars round to
+/// the nearest of an eighth, a half or a full block.
+fn bar_char(side: Side, eighths: u32) -> char {
+    let nearest = |eighth: char, half: char| match eighths {
+        0..=2 => eighth,
+        3..=6 => half,
+        _ => '█',
+    };
+    match side {
+        Side::Bottom if eighths == 4 => '▄',
+        Side::Bottom => char::from_u32('▁' as u32 + eighths - 1).unwrap(),
+        Side::Left if eighths == 4 => '▌',
+        Side::Left => char::from_u32(0x2590 - eighths).unwrap(),
+        Side::Top => nearest('▔', '▀'),
+        Side::Right => nearest('▕', '▐'),
+    }
+}
+
+/// Which half of a cell an enlarged sub-cell lies in
+enum Half {
+    First,
+    Second,
+    /// The sub-cell straddles the middle
+    Both,
+}
+
+fn half(index: usize, scale: usize) -> Half {
+    if 2 * (index + 1) <= scale {
+        Half::First
+    } else if 2 * index >= scale {
+        Half::Second
+    } else {
+        Half::Both
+    }
+}
+
+/// Filled quadrants (upper left, upper right, lower left, lower right) of
+/// the block elements made of whole quadrants
+fn quadrants(ch: char) -> Option<[bool; 4]> {
+    QUADRANT_CHARS
+        .iter()
+        .position(|&c| c == ch)
+        .map(|bits| [bits & 8 != 0, bits & 4 != 0, bits & 2 != 0, bits & 1 != 0])
+}
+
+pub(crate) fn quadrant_char([upper_left, upper_right, lower_left, lower_right]: [bool; 4]) -> char {
+    let bits = (upper_left as usize) << 3
+        | (upper_right as usize) << 2
+        | (lower_left as usize) << 1
+        | lower_right as usize;
+    QUADRANT_CHARS[bits]
+}
+
+/// Block elements indexed by their quadrants as the bits
+/// upper left, upper right, lower left, lower right
+const QUADRANT_CHARS: [char; 16] = [
+    ' ', '▗', '▖', '▄', '▝', '▐', '▞', '▟', '▘', '▚', '▌', '▙', '▀', '▜', '▛', '█',
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font::get_font;
+
+    #[test]
+    fn test_scale_blocks() {
+        assert_eq!(scale("█▀", 2, 2), "████\n██  ");
+        assert_eq!(scale("▌", 3, 1), "█▌ ");
+        assert_eq!(scale("▚", 2, 2), "█ \n █");
+        assert_eq!(scale("▄", 1, 4), " \n \n█\n█");
+        assert_eq!(scale("▂", 1, 2), " \n▄");
+    }
+
+    #[test]
+    fn test_scale_edge_eighths() {
+        assert_eq!(scale("▏", 2, 1), "▎ ");
+        assert_eq!(scale("▊", 2, 1), "█▌");
+        assert_eq!(scale("▏", 8, 1), "█       ");
+        assert_eq!(scale("▕", 4, 1), "   ▐");
+        assert_eq!(scale("▕", 2, 1), " ▕");
+        assert_eq!(scale("▔", 1, 4), "▀\n \n \n ");
+        assert_eq!(scale("▔", 1, 8), "█\n \n \n \n \n \n \n ");
+    }
+
+    #[test]
+    fn test_scale_box_drawing_extends_strokes() {
+        assert_eq!(scale("██╗\n╚═╝", 2, 2), "████╗ \n████║ \n╚═══╝ \n      ");
+        assert_eq!(scale("┏━", 3, 1), "┏━━━━━");
+    }
+
+    #[test]
+    fn test_rotate() {
+        assert_eq!(rotate("██╗\n╚═╝", Rotation::Quarter), "╔█\n║█\n╚╝");
+        assert_eq!(rotate("██╗\n╚═╝", Rotation::Half), "╔═╗\n╚██");
+        assert_eq!(rotate("██╗\n╚═╝", Rotation::ThreeQuarters), "╔╗\n█║\n█╝");
+        assert_eq!(rotate("▀▖▂", Rotation::Quarter), "▐\n▘\n▎");
+        assert_eq!(rotate("a-\nb", Rotation::Quarter), "ba\n |");
+        assert_eq!(rotate("╭╮\n╰╯", Rotation::Quarter), "╭╮\n╰╯");
+        assert_eq!(rotate("╭─", Rotation::Quarter), "╮\n│");
+    }
+
+    #[test]
+    fn test_four_quarter_turns_restore_art() {
+        let art = crate::renderer::render_text("BOX", "standard_shadow", 0, 7).unwrap();
+        let turned = (0..4).fold(art.clone(), |art, _| rotate(&art, Rotation::Quarter));
+        assert_eq!(turned, art);
+        assert_eq!(rotate(&rotate(&art, Rotation::Half), Rotation::Half), art);
+    }
+
+    #[test]
+    fn test_flip() {
+        assert_eq!(flip_horizontal("██╗\n╚═╝"), "╔██\n╚═╝");
+        assert_eq!(flip_vertical("██╗\n╚═╝"), "╔═╗\n██╝");
+        assert_eq!(flip_horizontal("▌▀▙"), "▟▀▐");
+        assert_eq!(flip_vertical("▌▀▙"), "▌▄▛");
+        assert_eq!(flip_horizontal("/(x"), "x)\\");
+        assert_eq!(flip_horizontal("ab\nc"), "ba\n c");
+        assert_eq!(flip_vertical("╭─╮"), "╰─╯");
+    }
+
+    #[test]
+    fn test_flips_compose_to_half_turn() {
+        let art = crate::renderer::render_text("FLIP", "standard_shadow", 0, 7).unwrap();
+        assert_eq!(flip_horizontal(&flip_horizontal(&art)), art);
+        assert_eq!(
+            flip_vertical(&flip_horizontal(&art)),
+            rotate(&art, Rotation::Half)
+        );
+    }
+
+    #[test]
+    fn test_drop_shadow_matches_built_in_font() {
+        let font = get_font("standard_shadow").unwrap();
+        for ch in ['A', 'H', 'O', '1'] {
+            let rows: Vec<Vec<char>> = font.characters[&ch]
+                .data
+                .iter()
+                .map(|row| row.chars().collect())
+                .collect();
+            let body: Vec<Vec<Option<char>>> = rows
+                .iter()
+                .map(|row| row.iter().map(|&c| Some(c).filter(|&c| c == '█')).collect())
+                .collect();
+            assert_eq!(drop_shadow(&body), rows, "{}", ch);
+        }
+    }
+
+    #[test]
+    fn test_shadow() {
+        let font = get_font("standard_shadow").unwrap();
+        assert_eq!(
+            shadow(&font.characters[&'H'].data),
+            font.characters[&'H'].data
+        );
+        assert_eq!(
+            shadow(&["██".to_string(), "  ".to_string()]),
+            ["██╗", "╚═╝"]
+        );
+    }
+
+    #[test]
+    fn test_left_shadow_font() {
+        let font = left_shadow_font(get_font("standard_shadow").unwrap());
+        assert_eq!(font.characters[&'I'].data[..2], ["╔██", "║██"]);
+        assert_eq!(font.characters[&'I'].data[5], "╚═╝");
+        assert_eq!(
+            font.characters[&'A'].width,
+            get_font("standard_shadow").unwrap().characters[&'A'].width
+        );
+
+        let solid = get_font("standard_solid").unwrap();
+        assert_eq!(
+            left_shadow_font(solid).characters[&'A'].data,
+            solid.characters[&'A'].data
+        );
+    }
+
+    #[test]
+    fn test_scale_font() {
+        let font = get_font("standard_shadow").unwrap();
+        let scaled = scale_font(font, 2, 3);
+        assert_eq!(scaled.height, font.height * 3);
+        let a = &scaled.characters[&'A'];
+        assert_eq!(a.data.len(), 21);
+        assert_eq!(a.width, font.characters[&'A'].width * 2);
+        assert_eq!(
+            a.data.join("\n"),
+            scale(&font.characters[&'A'].data.join("\n"), 2, 3)
+        );
+    }
+}